@@ -0,0 +1,156 @@
+//! Graph of the links between the articles, written out as the backlinks of each one.
+
+use std::collections::BTreeMap;
+
+use pulldown_cmark::{Event, LinkType, Parser, Tag, TagEnd};
+
+use crate::lore::{self, find_in, ArticleEntry, ArticleLink, Visibility, ARTICLE_OPTIONS};
+
+/// Whether the text ends a sentence before the character at `at`.
+fn ends_sentence(text: &str, at: usize) -> bool {
+    text[..at].ends_with(['.', '!', '?', '…']) && text[at..].starts_with(char::is_whitespace)
+}
+
+/// Sentence of the text that the byte offset falls into, with its spaces collapsed.
+fn sentence_around(text: &str, offset: usize) -> String {
+    let start = text
+        .char_indices()
+        .rev()
+        .map(|(i, _)| i)
+        .find(|&i| i <= offset && ends_sentence(text, i))
+        .unwrap_or(0);
+    let end = text
+        .char_indices()
+        .map(|(i, _)| i)
+        .find(|&i| i > offset && ends_sentence(text, i))
+        .unwrap_or(text.len());
+    text[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Articles that the article links to, each with the sentence around the link, in the
+/// order of the links.
+fn linked_articles<'a>(
+    articles: &'a [ArticleEntry],
+    base_path: Option<&str>,
+    article: &ArticleEntry,
+) -> Vec<(&'a ArticleEntry, String)> {
+    // Plain text of the blocks being read, innermost last, with the targets of their
+    // links and where the links start in the text.
+    let mut blocks: Vec<(String, Vec<(&ArticleEntry, usize)>)> = Vec::new();
+    let mut linked = Vec::new();
+
+    for event in Parser::new_ext(article.source, ARTICLE_OPTIONS) {
+        match event {
+            Event::Start(
+                Tag::Paragraph
+                | Tag::Heading { .. }
+                | Tag::Item
+                | Tag::TableCell
+                | Tag::DefinitionListTitle
+                | Tag::DefinitionListDefinition,
+            ) => blocks.push(Default::default()),
+            Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Heading(_)
+                | TagEnd::Item
+                | TagEnd::TableCell
+                | TagEnd::DefinitionListTitle
+                | TagEnd::DefinitionListDefinition,
+            ) => {
+                if let Some((text, links)) = blocks.pop() {
+                    linked.extend(
+                        links
+                            .into_iter()
+                            .map(|(target, offset)| (target, sentence_around(&text, offset))),
+                    );
+                }
+            }
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => {
+                let target = match link_type {
+                    LinkType::WikiLink { .. } => find_in(
+                        articles,
+                        dest_url.split_once('#').map_or(&dest_url, |(path, _)| path),
+                    ),
+                    _ => match lore::resolve(base_path, article.path, &dest_url) {
+                        ArticleLink::Article { path, .. } => {
+                            articles.iter().find(|article| article.path == path)
+                        }
+                        ArticleLink::Section(_) | ArticleLink::External(_) => None,
+                    },
+                };
+                if let (Some(target), Some((text, links))) = (target, blocks.last_mut()) {
+                    links.push((target, text.len()));
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((block, _)) = blocks.last_mut() {
+                    block.push_str(&text);
+                }
+            }
+            Event::SoftBreak | Event::HardBreak => {
+                if let Some((block, _)) = blocks.last_mut() {
+                    block.push(' ');
+                }
+            }
+            _ => {}
+        }
+    }
+    linked
+}
+
+/// Articles linking to each article, by the path of the linked one, sorted by title,
+/// each with the sentences around its links.
+///
+/// Unlisted articles are left out, so that they are only reachable by their links.
+fn backlinks<'a>(
+    articles: &'a [ArticleEntry],
+    base_path: Option<&str>,
+) -> BTreeMap<&'a str, Vec<(&'a ArticleEntry, Vec<String>)>> {
+    let mut backlinks = BTreeMap::<_, Vec<_>>::new();
+    for article in articles {
+        if article.meta.visibility == Visibility::Unlisted {
+            continue;
+        }
+        let mut outgoing = BTreeMap::<_, Vec<String>>::new();
+        for (target, sentence) in linked_articles(articles, base_path, article) {
+            if target.path == article.path {
+                continue;
+            }
+            let sentences = outgoing.entry(target.path).or_default();
+            if !sentences.contains(&sentence) {
+                sentences.push(sentence);
+            }
+        }
+        for (target, sentences) in outgoing {
+            backlinks
+                .entry(target)
+                .or_default()
+                .push((article, sentences));
+        }
+    }
+    for links in backlinks.values_mut() {
+        links.sort_by(|(a, _), (b, _)| a.title.cmp(&b.title));
+    }
+    backlinks
+}
+
+/// Source of the backlinks of every article, as `(article, [(linking article, [sentence])])`.
+pub fn source(articles: &[ArticleEntry], base_path: Option<&str>) -> String {
+    let mut source = String::from("&[\n");
+    for (target, links) in backlinks(articles, base_path) {
+        source.push_str(&format!("    ({target:?}, &[\n"));
+        for (from, sentences) in links {
+            source.push_str(&format!("        ({:?}, &{sentences:?}),\n", from.path));
+        }
+        source.push_str("    ]),\n");
+    }
+    source.push(']');
+    source
+}
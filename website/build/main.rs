@@ -0,0 +1,96 @@
+//! Embeds the sources of the articles in `resources/articles` and the list of the other
+//! files there, so that links can be resolved across the whole tree, and finds the
+//! backlinks of every article and the sections searched by the site.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+mod backlinks;
+mod search_index;
+
+// Shared with the site, which uses more of it than the build does.
+#[allow(dead_code)]
+#[path = "../src/lore/mod.rs"]
+mod lore;
+
+fn collect(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = fs::read_dir(dir).unwrap_or_else(|err| panic!("{}: {err}", dir.display()));
+    for entry in entries {
+        let path = entry.expect("article directory is readable").path();
+        if path.is_dir() {
+            collect(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+/// Path of the file from the articles directory, with `/` between the folders.
+fn relative(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .expect("file is inside the articles directory")
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Path the site is served under, from `Dioxus.toml`.
+fn base_path(manifest_dir: &Path) -> Option<String> {
+    let config = manifest_dir.join("Dioxus.toml");
+    println!("cargo:rerun-if-changed={}", config.display());
+    let config = fs::read_to_string(config).ok()?;
+    let config = config
+        .parse::<toml::Table>()
+        .expect("Dioxus.toml is valid TOML");
+    let base_path = config.get("web")?.get("app")?.get("base_path")?.as_str()?;
+    Some(base_path.to_owned())
+}
+
+fn main() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let root = manifest_dir.join("../resources/articles");
+    println!("cargo:rerun-if-changed={}", root.display());
+
+    let mut files = Vec::new();
+    collect(&root, &mut files);
+    files.sort();
+
+    let mut articles = String::from("&[\n");
+    let mut other_files = String::from("&[\n");
+    let mut entries = Vec::new();
+    for path in files {
+        let name = relative(&root, &path);
+        match name.strip_suffix(".md") {
+            Some(name) => {
+                let path = path.canonicalize().expect("article path exists");
+                articles.push_str(&format!("    ({name:?}, include_str!({path:?})),\n"));
+
+                // The build script ends right after, so the articles may live as long as it.
+                let source = fs::read_to_string(&path).expect("article is UTF-8 text");
+                entries.push(lore::ArticleEntry::new(
+                    Box::leak(name.to_owned().into_boxed_str()),
+                    Box::leak(source.into_boxed_str()),
+                ));
+            }
+            None => other_files.push_str(&format!("    {name:?},\n")),
+        }
+    }
+    articles.push(']');
+    other_files.push(']');
+
+    let out = PathBuf::from(env::var("OUT_DIR").expect("cargo sets OUT_DIR"));
+    fs::write(out.join("articles.rs"), articles).expect("OUT_DIR is writable");
+    fs::write(out.join("article_files.rs"), other_files).expect("OUT_DIR is writable");
+
+    let base_path = base_path(manifest_dir);
+    fs::write(
+        out.join("backlinks.rs"),
+        backlinks::source(&entries, base_path.as_deref()),
+    )
+    .expect("OUT_DIR is writable");
+    fs::write(out.join("search_index.rs"), search_index::source(&entries))
+        .expect("OUT_DIR is writable");
+}
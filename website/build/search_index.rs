@@ -0,0 +1,132 @@
+//! Sections of the articles with the stems of their words, searched by the site.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+};
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+use crate::lore::{outline, term, words, ArticleEntry, Visibility, ARTICLE_OPTIONS};
+
+/// Part of an article from one heading to the next.
+struct Section<'a> {
+    article: &'a str,
+
+    /// Id of the heading, empty for the text before the first heading.
+    id: String,
+    heading: String,
+    text: String,
+}
+
+impl Section<'_> {
+    /// Stems of the words of the heading, sorted.
+    fn heading_terms(&self) -> Vec<String> {
+        let terms = words(&self.heading).map(|(_, word)| term(word));
+        terms.collect::<BTreeSet<_>>().into_iter().collect()
+    }
+
+    /// Byte ranges in the text of the words with each stem.
+    fn terms(&self) -> BTreeMap<String, Vec<Range<usize>>> {
+        let mut terms = BTreeMap::<_, Vec<_>>::new();
+        for (range, word) in words(&self.text) {
+            terms.entry(term(word)).or_default().push(range);
+        }
+        terms
+    }
+}
+
+/// Sections of the article, with their plain text.
+fn sections(article: &ArticleEntry) -> Vec<Section<'_>> {
+    let mut ids = outline(article.source, ARTICLE_OPTIONS)
+        .into_iter()
+        .map(|entry| entry.id);
+    let section = |id| Section {
+        article: article.path,
+        id,
+        heading: String::new(),
+        text: String::new(),
+    };
+    let mut sections = vec![section(String::new())];
+    let mut in_heading = false;
+    let mut in_metadata = false;
+
+    for event in Parser::new_ext(article.source, ARTICLE_OPTIONS) {
+        if let Event::Start(Tag::Heading { .. }) = event {
+            sections.push(section(ids.next().unwrap_or_default()));
+            in_heading = true;
+            continue;
+        }
+        let section = sections
+            .last_mut()
+            .expect("sections start with the text before the first heading");
+        match event {
+            Event::End(TagEnd::Heading(_)) => in_heading = false,
+            Event::Start(Tag::MetadataBlock(_)) => in_metadata = true,
+            Event::End(TagEnd::MetadataBlock(_)) => in_metadata = false,
+            Event::Text(text) | Event::Code(text) if !in_metadata => {
+                let target = if in_heading {
+                    &mut section.heading
+                } else {
+                    &mut section.text
+                };
+                target.push_str(&text);
+            }
+            // Words of adjacent blocks and lines stay apart.
+            Event::SoftBreak
+            | Event::HardBreak
+            | Event::End(
+                TagEnd::Paragraph
+                | TagEnd::Item
+                | TagEnd::TableCell
+                | TagEnd::CodeBlock
+                | TagEnd::DefinitionListTitle
+                | TagEnd::DefinitionListDefinition,
+            ) if !in_heading && !section.text.is_empty() && !section.text.ends_with(' ') => {
+                section.text.push(' ');
+            }
+            _ => {}
+        }
+    }
+
+    sections.retain(|section| !section.heading.is_empty() || !section.text.trim().is_empty());
+    sections
+}
+
+/// Source of the sections of every listed article, as `Section` literals with their
+/// stems sorted.
+pub fn source(articles: &[ArticleEntry]) -> String {
+    let mut source = String::from("&[\n");
+    for section in articles
+        .iter()
+        .filter(|article| article.meta.visibility != Visibility::Unlisted)
+        .flat_map(sections)
+    {
+        let Section {
+            article,
+            id,
+            heading,
+            text,
+        } = &section;
+        source.push_str("    Section {\n");
+        source.push_str(&format!("        article: {article:?},\n"));
+        source.push_str(&format!("        id: {id:?},\n"));
+        source.push_str(&format!("        heading: {heading:?},\n"));
+        source.push_str(&format!("        text: {text:?},\n"));
+        source.push_str(&format!(
+            "        heading_terms: &{:?},\n",
+            section.heading_terms()
+        ));
+        source.push_str("        terms: &[\n");
+        for (term, ranges) in section.terms() {
+            let ranges = ranges
+                .into_iter()
+                .map(|range| (range.start, range.end))
+                .collect::<Vec<_>>();
+            source.push_str(&format!("            ({term:?}, &{ranges:?}),\n"));
+        }
+        source.push_str("        ],\n    },\n");
+    }
+    source.push(']');
+    source
+}
@@ -0,0 +1,169 @@
+//! Parts of the article page built from its front matter and outline.
+
+use dioxus::prelude::*;
+use pulldown_cmark::HeadingLevel;
+
+use crate::{
+    character::ArticleStatBlock,
+    link::{article_route, backlinks},
+    lore::{ArticleMeta, OutlineEntry, Visibility},
+};
+
+/// Fewest sections for an article to get a table of contents.
+const TOC_MIN_SECTIONS: usize = 3;
+
+/// Title, notices and tags shown above the article.
+#[component]
+pub fn ArticleHeader(meta: ArticleMeta) -> Element {
+    rsx! {
+        if let Some(title) = &meta.title {
+            document::Title { "{title}" }
+        }
+        if let Some(summary) = &meta.summary {
+            document::Meta { name: "description", content: "{summary}" }
+        }
+        if meta.visibility == Visibility::Draft {
+            div {
+                class: "px-4 py-2 bg-yellow-100 text-yellow-800",
+                "This article is a draft"
+            }
+        }
+        if !meta.tags.is_empty() {
+            div {
+                class: "flex flex-wrap gap-2",
+                for tag in &meta.tags {
+                    span { class: "px-2 bg-gray-300 rounded", "{tag}" }
+                }
+            }
+        }
+    }
+}
+
+/// Stat block, other names and related characters shown beside the article.
+#[component]
+pub fn ArticleAside(meta: ArticleMeta) -> Element {
+    let bound = meta.character.is_some() || meta.character_code.is_some();
+    if !bound && meta.aliases.is_empty() && meta.related_characters.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4 w-72 shrink-0",
+            if bound {
+                ArticleStatBlock { id: meta.character, code: meta.character_code }
+            }
+            if !meta.aliases.is_empty() {
+                div {
+                    class: "flex flex-col",
+                    span { class: "text-xl", "Also Known As" }
+                    for alias in &meta.aliases {
+                        span { "{alias}" }
+                    }
+                }
+            }
+            if !meta.related_characters.is_empty() {
+                div {
+                    class: "flex flex-col",
+                    span { class: "text-xl", "Related Characters" }
+                    for path in &meta.related_characters {
+                        Link {
+                            class: "hover:underline",
+                            to: article_route(path, ""),
+                            {path.rsplit('/').next().unwrap_or(path)}
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Articles that link to the article at the path, with the sentences around their links.
+#[component]
+pub fn Backlinks(path: String) -> Element {
+    let backlinks = backlinks(&path);
+    if backlinks.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 pt-4 border-t border-gray-300",
+            span { class: "text-xl", "Linked From" }
+            for backlink in backlinks {
+                div {
+                    key: "{backlink.from.path}",
+                    class: "flex flex-col",
+                    Link {
+                        class: "hover:underline",
+                        to: article_route(backlink.from.path, ""),
+                        "{backlink.from.title}"
+                    }
+                    for sentence in &backlink.sentences {
+                        span { class: "pl-4 text-sm text-gray-600", "{sentence}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn scroll_into_view(id: &str) {
+    let id = serde_json::to_string(id).expect("element id serializes to JSON");
+    document::eval(&format!(
+        "document.getElementById({id})?.scrollIntoView({{ behavior: 'smooth' }});"
+    ));
+}
+
+/// Scrolls to the element and records its id in the URL fragment, without a navigation.
+pub fn scroll_to_anchor(id: &str) {
+    scroll_into_view(id);
+    let id = serde_json::to_string(id).expect("element id serializes to JSON");
+    document::eval(&format!(
+        "history.replaceState(history.state, '', '#' + encodeURIComponent({id}));"
+    ));
+}
+
+/// Sticky list of the sections of a long article, the title left out.
+///
+/// The article is scrolled to the `section` of the route whenever it changes.
+#[component]
+pub fn TableOfContents(outline: Vec<OutlineEntry>, section: String) -> Element {
+    use_effect(use_reactive!(|section| {
+        if !section.is_empty() {
+            scroll_into_view(&section);
+        }
+    }));
+
+    let sections = outline
+        .into_iter()
+        .filter(|entry| entry.level != HeadingLevel::H1)
+        .collect::<Vec<_>>();
+    if sections.len() < TOC_MIN_SECTIONS {
+        return rsx! {};
+    }
+
+    rsx! {
+        nav {
+            class: "flex flex-col gap-1 w-56 shrink-0 sticky top-0 max-h-screen overflow-auto",
+            span { class: "text-xl", "Contents" }
+            for entry in sections {
+                a {
+                    key: "{entry.id}",
+                    class: match entry.level {
+                        HeadingLevel::H1 | HeadingLevel::H2 => "hover:underline",
+                        HeadingLevel::H3 => "pl-4 hover:underline",
+                        _ => "pl-8 text-sm hover:underline",
+                    },
+                    href: "#{entry.id}",
+                    onclick: move |e| {
+                        e.prevent_default();
+                        scroll_to_anchor(&entry.id);
+                    },
+                    "{entry.text}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,52 @@
+//! Index of the articles in `resources/articles`, embedded at build time.
+
+use std::sync::LazyLock;
+
+use crate::lore::{find_in, ArticleEntry};
+
+/// Path without the extension and source of every article, sorted by path.
+static ARTICLE_SOURCES: &[(&str, &str)] = include!(concat!(env!("OUT_DIR"), "/articles.rs"));
+
+/// Paths of the other files next to the articles, such as images.
+static ARTICLE_FILES: &[&str] = include!(concat!(env!("OUT_DIR"), "/article_files.rs"));
+
+static ARTICLES: LazyLock<Vec<ArticleEntry>> = LazyLock::new(|| {
+    ARTICLE_SOURCES
+        .iter()
+        .map(|&(path, source)| ArticleEntry::new(path, source))
+        .collect()
+});
+
+pub fn articles() -> &'static [ArticleEntry] {
+    &ARTICLES
+}
+
+/// Article at the path, such as `characters/Nitros`.
+pub fn article(path: &str) -> Option<&'static ArticleEntry> {
+    articles().iter().find(|article| article.path == path)
+}
+
+/// Whether a file other than an article is at the path, such as `maps/world.png`.
+pub fn has_file(path: &str) -> bool {
+    ARTICLE_FILES.contains(&path)
+}
+
+/// Article named by the target of a wiki link, ignoring case.
+///
+/// The target is matched against the paths of the articles, then their file names,
+/// titles and aliases, so that a file name wins over an alias of another article.
+pub fn find_article(target: &str) -> Option<&'static ArticleEntry> {
+    find_in(articles(), target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree_is_embedded() {
+        let nitros = find_article("Nitros").unwrap();
+        assert_eq!(nitros.path, "characters/Nitros");
+        assert_eq!(nitros.title, "Нитрос");
+    }
+}
@@ -0,0 +1,169 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+use super::feats::FeatPicker;
+
+/// Background of the character, or a story-only origin.
+#[component]
+pub fn BackgroundPanel(character: Signal<props::Character>) -> Element {
+    let current = character.peek().background().cloned();
+
+    let mut name = use_signal(|| {
+        current
+            .as_ref()
+            .map_or(String::new(), |background| background.name.clone())
+    });
+    let mut abilities = use_signal(|| {
+        current
+            .as_ref()
+            .map_or(Vec::new(), |background| background.abilities.clone())
+    });
+    let mut tool = use_signal(|| {
+        current
+            .as_ref()
+            .and_then(|background| background.tool.clone())
+            .unwrap_or_default()
+    });
+    let mut gold = use_signal(|| {
+        current
+            .as_ref()
+            .is_some_and(|background| background.equipment == props::StartingEquipment::Gold)
+    });
+    let mut feat = use_signal(|| {
+        current
+            .as_ref()
+            .map_or(props::ChosenFeat::new(""), |background| {
+                background.feat.clone()
+            })
+    });
+    let mut error = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let selected = props::Background::find(&name.read());
+    let (equipment, equipment_gold) = c.starting_equipment();
+
+    let select_background = move |e: Event<FormData>| {
+        let background = props::Background::find(&e.value());
+        name.set(e.value());
+        abilities.set(background.map_or(Vec::new(), |background| background.abilities.to_vec()));
+        tool.set(String::new());
+        feat.set(props::ChosenFeat::new(
+            background.map_or("", |background| background.feat),
+        ));
+    };
+
+    let apply = move |_| {
+        if name.read().is_empty() {
+            character.write().clear_background();
+            error.set(None);
+            return;
+        }
+
+        let chosen = props::ChosenBackground {
+            name: name(),
+            abilities: abilities(),
+            feat: feat(),
+            tool: Some(tool.read().trim().to_owned()).filter(|tool| !tool.is_empty()),
+            equipment: if gold() {
+                props::StartingEquipment::Gold
+            } else {
+                props::StartingEquipment::Package
+            },
+        };
+        let result = character.write().set_background(chosen);
+        error.set(result.err().map(|err| err.to_string()));
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Background" }
+            div {
+                class: "flex flex-wrap gap-4 items-center",
+                select {
+                    onchange: select_background,
+                    option { value: "", selected: selected.is_none(), "Story only" }
+                    for option in props::Background::all() {
+                        option {
+                            value: option.name,
+                            selected: option.name == *name.read(),
+                            "{option.name}"
+                        }
+                    }
+                }
+                input {
+                    r#type: "text",
+                    placeholder: "Origin",
+                    value: "{c.origin}",
+                    oninput: move |e| character.write().origin = e.value(),
+                }
+            }
+            for background in selected {
+                div {
+                    class: "flex flex-wrap gap-2 items-center",
+                    "Skills: {background.skills[0]:?}, {background.skills[1]:?}"
+                    match background.tool {
+                        props::BackgroundTool::Fixed(tool) => rsx! { span { "Tool: {tool}" } },
+                        props::BackgroundTool::Choice(kind) => rsx! {
+                            input {
+                                r#type: "text",
+                                placeholder: "{kind}",
+                                value: "{tool}",
+                                oninput: move |e| tool.set(e.value()),
+                            }
+                        },
+                    }
+                }
+                div {
+                    class: "flex flex-wrap gap-2 items-center",
+                    for (index, ability) in abilities().into_iter().enumerate() {
+                        select {
+                            onchange: move |e| {
+                                if let Ok(value) = e.parsed() {
+                                    abilities.write()[index] = value;
+                                }
+                            },
+                            for option in background.abilities {
+                                option {
+                                    value: "{option:?}",
+                                    selected: option == ability,
+                                    "+1 {option.abbreviation()}"
+                                }
+                            }
+                        }
+                    }
+                }
+                FeatPicker {
+                    key: "{background.name}",
+                    feat,
+                    categories: vec![props::FeatCategory::Origin],
+                    names: vec![background.feat],
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: gold(),
+                        onchange: move |e| gold.set(e.checked()),
+                    }
+                    " Take {props::BACKGROUND_GOLD} GP instead of the equipment"
+                }
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+            div {
+                class: "flex gap-4",
+                button { onclick: apply, "Apply Background" }
+            }
+            if let Some(background) = c.background() {
+                div {
+                    "{background.name}: {equipment.join(\", \")}"
+                    if equipment_gold > 0 {
+                        ", {equipment_gold} GP"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,204 @@
+use dioxus::prelude::*;
+
+use crate::{props, Route};
+
+use super::{
+    stat_block::StatBlock,
+    storage::{self, IndexEntry},
+};
+
+/// One side of a comparison: a stored character, either as it is now or after
+/// the given number of edits of its journal.
+///
+/// Written in the route as the id, followed by `@` and the number of edits for a version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct Side {
+    id: String,
+    version: Option<usize>,
+}
+
+impl Side {
+    fn parse(spec: &str) -> Self {
+        match spec.split_once('@') {
+            Some((id, version)) => Side {
+                id: id.to_owned(),
+                version: version.parse().ok(),
+            },
+            None => Side {
+                id: spec.to_owned(),
+                version: None,
+            },
+        }
+    }
+
+    fn spec(&self) -> String {
+        match self.version {
+            Some(version) => format!("{}@{version}", self.id),
+            None => self.id.clone(),
+        }
+    }
+
+    fn title(&self, character: &props::Character) -> String {
+        match self.version {
+            None => character.name.clone(),
+            Some(0) => format!("{}, before the first edit", character.name),
+            Some(version) => format!("{}, after edit {version}", character.name),
+        }
+    }
+
+    fn load(&self) -> Result<props::Character, String> {
+        let character = storage::load_character(&self.id)
+            .ok_or_else(|| format!("No character {} is stored", self.id))?
+            .map_err(|err| err.to_string())?;
+        match self.version {
+            None => Ok(character),
+            Some(version) => storage::load_journal(&self.id)
+                .restore(version, &character)
+                .map_err(|err| err.to_string()),
+        }
+    }
+}
+
+/// Two stored characters, or two versions of one, side by side with their differences.
+#[component]
+pub fn Compare(left: String, right: String) -> Element {
+    let index = storage::load_index();
+    let (left_side, right_side) = (Side::parse(&left), Side::parse(&right));
+    let loaded = |side: &Side| (!side.id.is_empty()).then(|| side.load());
+    let (left_character, right_character) = (loaded(&left_side), loaded(&right_side));
+
+    let (left_spec, right_spec) = (left.clone(), right.clone());
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-4 py-4 w-full",
+            span { class: "text-3xl", "Compare Characters" }
+            div {
+                class: "grid grid-cols-2 gap-8",
+                SidePicker {
+                    index: index.clone(),
+                    side: left_side.clone(),
+                    character: left_character.clone(),
+                    on_change: move |spec| {
+                        navigator().replace(Route::Compare { left: spec, right: right_spec.clone() });
+                    },
+                }
+                SidePicker {
+                    index,
+                    side: right_side.clone(),
+                    character: right_character.clone(),
+                    on_change: move |spec| {
+                        navigator().replace(Route::Compare { left: left_spec.clone(), right: spec });
+                    },
+                }
+            }
+            if let (Some(Ok(old)), Some(Ok(new))) = (left_character, right_character) {
+                ChangesTable {
+                    old_title: left_side.title(&old),
+                    new_title: right_side.title(&new),
+                    old,
+                    new,
+                }
+            }
+        }
+    }
+}
+
+#[component]
+fn SidePicker(
+    index: Vec<IndexEntry>,
+    side: Side,
+    character: Option<Result<props::Character, String>>,
+    on_change: EventHandler<String>,
+) -> Element {
+    let journal = storage::load_journal(&side.id);
+    let id = side.id.clone();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2",
+            select {
+                onchange: move |e| on_change(e.value()),
+                option { value: "", selected: side.id.is_empty(), "Choose a character" }
+                for entry in index {
+                    option {
+                        value: "{entry.id}",
+                        selected: entry.id == side.id,
+                        "{entry.name} ({entry.classes_text()})"
+                    }
+                }
+            }
+            if !journal.edits().is_empty() {
+                select {
+                    onchange: move |e| {
+                        let version = e.value().parse().ok();
+                        on_change(Side { id: id.clone(), version }.spec());
+                    },
+                    option { value: "", selected: side.version.is_none(), "Current" }
+                    for applied in (1..=journal.edits().len()).rev() {
+                        option {
+                            value: "{applied}",
+                            selected: side.version == Some(applied),
+                            "After edit {applied}, {storage::format_timestamp(journal.edits()[applied - 1].timestamp)}"
+                        }
+                    }
+                    option { value: "0", selected: side.version == Some(0), "Before the first recorded edit" }
+                }
+            }
+            match character {
+                Some(Ok(character)) => rsx! {
+                    StatBlock { character, id: side.id.clone() }
+                },
+                Some(Err(error)) => rsx! {
+                    div { class: "text-red-700", "{error}" }
+                },
+                None => rsx! {},
+            }
+        }
+    }
+}
+
+/// Fields that differ between two characters, the old values in red and the new in green.
+#[component]
+pub fn ChangesTable(
+    old: props::Character,
+    new: props::Character,
+    old_title: String,
+    new_title: String,
+) -> Element {
+    let changes = old.diff(&new);
+    if changes.is_empty() {
+        return rsx! {
+            div { "No differences" }
+        };
+    }
+
+    rsx! {
+        table {
+            class: "table-auto text-left",
+            thead {
+                tr {
+                    th { class: "px-2", "Field" }
+                    th { class: "px-2", "{old_title}" }
+                    th { class: "px-2", "{new_title}" }
+                }
+            }
+            tbody {
+                for change in changes {
+                    tr {
+                        key: "{change.path}",
+                        td { class: "px-2", title: "{change.path}", "{change.label()}" }
+                        td {
+                            class: if change.old.is_some() { "px-2 bg-red-100" } else { "px-2 text-gray-500" },
+                            "{props::value_text(change.old.as_ref())}"
+                        }
+                        td {
+                            class: if change.new.is_some() { "px-2 bg-green-100" } else { "px-2 text-gray-500" },
+                            "{props::value_text(change.new.as_ref())}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Conditions, exhaustion and the d20 tests they affect.
+#[component]
+pub fn ConditionsPanel(character: Signal<props::Character>) -> Element {
+    let mut condition = use_signal(|| props::Condition::Blinded);
+    let mut source = use_signal(String::new);
+    let mut rounds = use_signal(String::new);
+    let mut result = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let mut tests = vec![
+        props::D20Test::Initiative,
+        props::D20Test::AttackRoll(props::Ability::Strength),
+        props::D20Test::AttackRoll(props::Ability::Dexterity),
+    ];
+    tests.extend(props::Ability::ALL.map(props::D20Test::AbilityCheck));
+    tests.extend(props::Ability::ALL.map(props::D20Test::SavingThrow));
+    let rolls = tests
+        .into_iter()
+        .map(|test| c.d20_roll(test))
+        .collect::<Vec<_>>();
+
+    let add = move |_| {
+        let source = source.read().trim().to_owned();
+        character.write().add_condition(props::ActiveCondition {
+            condition: condition(),
+            source: (!source.is_empty()).then_some(source),
+            rounds: rounds.read().trim().parse().ok(),
+        });
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Conditions" }
+            div {
+                class: "flex gap-4 items-center",
+                span { "Exhaustion {c.exhaustion()}/{props::MAX_EXHAUSTION}" }
+                button {
+                    onclick: move |_| {
+                        let level = character.read().exhaustion().saturating_sub(1);
+                        character.write().set_exhaustion(level);
+                    },
+                    "-"
+                }
+                button {
+                    onclick: move |_| {
+                        let level = character.read().exhaustion() + 1;
+                        character.write().set_exhaustion(level);
+                    },
+                    "+"
+                }
+            }
+            ul {
+                for (index, active) in c.conditions().iter().enumerate() {
+                    li {
+                        class: "flex gap-4 items-center",
+                        span { "{active}" }
+                        button {
+                            onclick: move |_| {
+                                character.write().remove_condition(index);
+                            },
+                            "Remove"
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                select {
+                    onchange: move |e| {
+                        if let Ok(value) = e.parsed() {
+                            condition.set(value);
+                        }
+                    },
+                    for option in props::Condition::ALL {
+                        option { value: "{option:?}", selected: option == condition(), "{option:?}" }
+                    }
+                }
+                input {
+                    r#type: "text",
+                    placeholder: "Source",
+                    value: "{source}",
+                    oninput: move |e| source.set(e.value()),
+                }
+                input {
+                    r#type: "number",
+                    min: 1,
+                    placeholder: "Rounds",
+                    value: "{rounds}",
+                    oninput: move |e| rounds.set(e.value()),
+                }
+                button { onclick: add, "Add Condition" }
+                button {
+                    onclick: move |_| {
+                        character.write().end_round();
+                    },
+                    "End Round"
+                }
+            }
+            div {
+                class: "grid grid-cols-3 gap-2",
+                for roll in rolls {
+                    div {
+                        class: "flex gap-2 items-center",
+                        title: roll.modifiers.iter().map(|(modifier, source)| format!("{modifier:?} from {source}")).collect::<Vec<_>>().join(", "),
+                        span { "{roll}" }
+                        button {
+                            onclick: {
+                                let test = roll.test;
+                                move |_| {
+                                    let rolled = character.read().d20_roll(test).roll();
+                                    result.set(Some(format!("{test}: {rolled}")));
+                                }
+                            },
+                            "Roll"
+                        }
+                    }
+                }
+            }
+            if let Some(result) = result() {
+                div { "{result}" }
+            }
+        }
+    }
+}
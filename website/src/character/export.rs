@@ -0,0 +1,101 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use dioxus::prelude::*;
+
+use crate::{props, Route};
+
+/// Scheme, host and port the site is served from, such as `https://example.org`.
+#[cfg(target_family = "wasm")]
+fn origin() -> String {
+    let location = js_sys::Reflect::get(&js_sys::global(), &"location".into());
+    location
+        .and_then(|location| js_sys::Reflect::get(&location, &"origin".into()))
+        .ok()
+        .and_then(|origin| origin.as_string())
+        .unwrap_or_default()
+}
+
+#[cfg(not(target_family = "wasm"))]
+fn origin() -> String {
+    String::new()
+}
+
+/// Absolute link that opens the character list with the character ready to import,
+/// for the site served at `origin` under `base_path`.
+fn share_link(origin: &str, base_path: Option<&str>, character: &props::Character) -> String {
+    let route = Route::CharactersList {
+        share: character.to_share_code(),
+    };
+    match base_path.map(|base| base.trim_matches('/')) {
+        Some(base) if !base.is_empty() => format!("{origin}/{base}{route}"),
+        _ => format!("{origin}{route}"),
+    }
+}
+
+/// Link that downloads the content as a file.
+fn data_url(mime: &str, content: &[u8]) -> String {
+    format!("data:{mime};base64,{}", STANDARD.encode(content))
+}
+
+/// Downloads of the character and its sheets, and a link to share it.
+#[component]
+pub fn ExportPanel(character: Signal<props::Character>) -> Element {
+    let c = character.read();
+    let file_name = match c.name.trim() {
+        "" => "character".to_owned(),
+        name => name.to_owned(),
+    };
+    let json =
+        serde_json::to_string_pretty(&c.to_json_value()).expect("character serializes to JSON");
+    let share = share_link(&origin(), dioxus::cli_config::base_path().as_deref(), &c);
+
+    rsx! {
+        div {
+            class: "flex gap-4 items-center py-4",
+            span { class: "text-2xl", "Export" }
+            a {
+                href: data_url("application/json", json.as_bytes()),
+                download: "{file_name}.json",
+                "Download JSON"
+            }
+            a {
+                href: data_url("application/octet-stream", &c.to_binary()),
+                download: "{file_name}.bin",
+                "Download Binary"
+            }
+            a {
+                href: data_url("text/markdown", c.to_markdown().as_bytes()),
+                download: "{file_name}.md",
+                "Download Sheet"
+            }
+            a {
+                href: data_url("text/html", c.to_html().as_bytes()),
+                download: "{file_name}.html",
+                "Printable Sheet"
+            }
+            a {
+                href: "{share}",
+                title: "Copy this link to share the character",
+                "Share Link"
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_links_are_absolute_and_round_trip() {
+        let mut character = props::Character::new();
+        character.name = "Nitros".to_owned();
+
+        let link = share_link("https://example.org", Some("my-dnd-world"), &character);
+        let (page, code) = link.split_once('#').unwrap();
+        assert_eq!(page, "https://example.org/my-dnd-world/characters");
+        assert_eq!(props::Character::from_share_code(code).unwrap(), character);
+
+        let link = share_link("http://localhost:8080", None, &character);
+        assert!(link.starts_with("http://localhost:8080/characters#"));
+    }
+}
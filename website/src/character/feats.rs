@@ -0,0 +1,297 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Picks a feat of the given categories together with its sub-choices.
+#[component]
+pub fn FeatPicker(
+    feat: Signal<props::ChosenFeat>,
+    categories: Vec<props::FeatCategory>,
+    /// Restricts the picker to these feats when not empty.
+    #[props(default)]
+    names: Vec<&'static str>,
+) -> Element {
+    let mut name = use_signal(|| feat.peek().name.clone());
+    let mut abilities = use_signal(|| {
+        let chosen = feat.peek();
+        let mut abilities = chosen.abilities().collect::<Vec<_>>();
+        if let Some(feat) = chosen.feat() {
+            if let Some(&first) = feat
+                .ability_options()
+                .and_then(|(options, _)| options.first())
+            {
+                abilities.resize(feat.ability_picks() as usize, first);
+            }
+        }
+        abilities
+    });
+    let mut proficiencies = use_signal(|| {
+        let chosen = feat.peek();
+        let mut proficiencies = chosen
+            .proficiencies()
+            .map(|proficiency| proficiency.to_string())
+            .collect::<Vec<_>>();
+        if let Some((count, _)) = chosen.feat().and_then(|feat| feat.proficiency_picks()) {
+            proficiencies.resize(count as usize, String::new());
+        }
+        proficiencies
+    });
+    let mut spells = use_signal(|| {
+        let chosen = feat.peek();
+        let mut spells = chosen.spells().map(str::to_owned).collect::<Vec<_>>();
+        if let Some(feat) = chosen.feat() {
+            spells.resize(feat.spell_picks() as usize, String::new());
+        }
+        spells
+    });
+
+    use_effect(move || {
+        let name = name();
+        let kind = props::Feat::find(&name)
+            .and_then(|feat| feat.proficiency_picks())
+            .map_or(props::ProficiencyKind::SkillOrTool, |(_, kind)| kind);
+
+        let mut choices = Vec::new();
+        choices.extend(abilities().into_iter().map(props::FeatChoice::Ability));
+        choices.extend(
+            proficiencies()
+                .iter()
+                .filter_map(|text| kind.parse(text))
+                .map(props::FeatChoice::Proficiency),
+        );
+        choices.extend(
+            spells()
+                .iter()
+                .map(|spell| spell.trim())
+                .filter(|spell| !spell.is_empty())
+                .map(|spell| props::FeatChoice::Spell(spell.to_owned())),
+        );
+
+        feat.set(props::ChosenFeat { name, choices });
+    });
+
+    let selected = props::Feat::find(&name.read());
+    let ability_options = selected
+        .and_then(|feat| feat.ability_options())
+        .map_or(&[][..], |(abilities, _)| abilities);
+    let (proficiency_picks, kind) = selected
+        .and_then(|feat| feat.proficiency_picks())
+        .unwrap_or((0, props::ProficiencyKind::SkillOrTool));
+    let spell_picks = selected.map_or(0, |feat| feat.spell_picks());
+
+    let select_feat = move |e: Event<FormData>| {
+        let feat = props::Feat::find(&e.value());
+        let picks = feat.map_or(0, |feat| feat.ability_picks());
+        let first = feat
+            .and_then(|feat| feat.ability_options())
+            .and_then(|(abilities, _)| abilities.first().copied());
+
+        name.set(e.value());
+        abilities.set(first.map_or(Vec::new(), |first| vec![first; picks as usize]));
+        proficiencies.set(vec![
+            String::new();
+            feat.map_or(0, |feat| {
+                feat.proficiency_picks().map_or(0, |(count, _)| count) as usize
+            })
+        ]);
+        spells.set(vec![
+            String::new();
+            feat.map_or(0, |feat| feat.spell_picks()) as usize
+        ]);
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-wrap gap-2 items-center",
+            select {
+                onchange: select_feat,
+                option { value: "", selected: selected.is_none(), "Choose a feat" }
+                for option in props::Feat::all().iter().filter(|feat| {
+                    categories.contains(&feat.category) && (names.is_empty() || names.contains(&feat.name))
+                }) {
+                    option {
+                        value: option.name,
+                        selected: option.name == *name.read(),
+                        "{option.name} ({option.category:?})"
+                    }
+                }
+            }
+            for (index, ability) in abilities().into_iter().enumerate() {
+                select {
+                    onchange: move |e| {
+                        if let Ok(value) = e.parsed() {
+                            abilities.write()[index] = value;
+                        }
+                    },
+                    for &option in ability_options {
+                        option {
+                            value: "{option:?}",
+                            selected: option == ability,
+                            "+1 {option.abbreviation()}"
+                        }
+                    }
+                }
+            }
+            for index in 0..proficiency_picks as usize {
+                if kind == props::ProficiencyKind::SavingThrow {
+                    select {
+                        onchange: move |e| proficiencies.write()[index] = e.value(),
+                        option { value: "", "Saving throw" }
+                        for option in props::Ability::ALL {
+                            option {
+                                value: "{option:?}",
+                                selected: proficiencies.read().get(index).is_some_and(|text| *text == format!("{option:?}")),
+                                "{option:?}"
+                            }
+                        }
+                    }
+                } else {
+                    input {
+                        r#type: "text",
+                        list: "skills",
+                        placeholder: "{kind:?}",
+                        value: proficiencies.read().get(index).cloned().unwrap_or_default(),
+                        oninput: move |e| proficiencies.write()[index] = e.value(),
+                    }
+                }
+            }
+            for index in 0..spell_picks as usize {
+                input {
+                    r#type: "text",
+                    placeholder: "Spell",
+                    value: spells.read().get(index).cloned().unwrap_or_default(),
+                    oninput: move |e| spells.write()[index] = e.value(),
+                }
+            }
+            datalist {
+                id: "skills",
+                for skill in props::Skill::ALL {
+                    option { value: "{skill:?}" }
+                }
+            }
+        }
+    }
+}
+
+/// Feats of the character and where they were gained.
+#[component]
+pub fn FeatsPanel(character: Signal<props::Character>) -> Element {
+    let granted = use_signal(|| props::ChosenFeat::new(""));
+    let mut error = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let mut granted_index = 0;
+    let feats = c
+        .feats()
+        .map(|(source, feat)| {
+            let index = match source {
+                props::FeatSource::Level(_) | props::FeatSource::Background => None,
+                props::FeatSource::Granted => {
+                    granted_index += 1;
+                    Some(granted_index - 1)
+                }
+            };
+            (source, feat.to_string(), index)
+        })
+        .collect::<Vec<_>>();
+
+    let grant = move |_| {
+        let result = character.write().grant_feat(granted());
+        error.set(result.err().map(|err| err.to_string()));
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Feats" }
+            ul {
+                for (source, feat, index) in feats {
+                    li {
+                        class: "flex gap-4 items-center",
+                        span { "{feat}" }
+                        match source {
+                            props::FeatSource::Level(level) => rsx! { span { class: "text-gray-500", "Level {level}" } },
+                            props::FeatSource::Background => rsx! { span { class: "text-gray-500", "Background" } },
+                            props::FeatSource::Granted => rsx! { span { class: "text-gray-500", "Granted" } },
+                        }
+                        if let Some(index) = index {
+                            button {
+                                onclick: move |_| {
+                                    character.write().remove_granted_feat(index);
+                                },
+                                "Remove"
+                            }
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                FeatPicker {
+                    feat: granted,
+                    categories: vec![
+                        props::FeatCategory::Origin,
+                        props::FeatCategory::General,
+                        props::FeatCategory::FightingStyle,
+                        props::FeatCategory::EpicBoon,
+                    ],
+                }
+                button { onclick: grant, "Grant Feat" }
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+        }
+    }
+}
+
+fn breakdown(bonuses: &[props::Bonus]) -> String {
+    bonuses
+        .iter()
+        .map(|bonus| bonus.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Derived statistics with the source of every bonus.
+#[component]
+pub fn StatsPanel(character: Signal<props::Character>) -> Element {
+    let c = character.read();
+
+    let hit_points = c.hit_point_bonuses();
+    let initiative = c.initiative_bonuses();
+    let speed = c.speed_bonuses();
+    let proficiencies = c.proficiencies();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Statistics" }
+            div {
+                class: "grid grid-cols-6 gap-4",
+                for ability in props::Ability::ALL {
+                    div {
+                        class: "flex flex-col items-center",
+                        title: breakdown(&c.ability_score_bonuses(ability)),
+                        span { class: "text-sm", "{ability.abbreviation()}" }
+                        span { class: "text-3xl", "{c.ability_score(ability)}" }
+                        span { "{c.ability_modifier(ability):+}" }
+                        span { class: "text-xs text-gray-500", "base {c.base_ability_score(ability)}" }
+                        for bonus in c.ability_score_bonuses(ability) {
+                            span { class: "text-xs text-gray-500", "{bonus}" }
+                        }
+                    }
+                }
+            }
+            div { title: breakdown(&hit_points), "Max HP {c.max_hit_points()}: {breakdown(&hit_points)}" }
+            div { title: breakdown(&initiative), "Initiative {props::total(&initiative):+}: {breakdown(&initiative)}" }
+            div { title: breakdown(&speed), "Speed {c.speed()} ft.: {breakdown(&speed)}" }
+            div { "Proficiency bonus {c.proficiency_bonus():+}" }
+            ul {
+                for (proficiency, source) in proficiencies {
+                    li { "{proficiency} ({source})" }
+                }
+            }
+        }
+    }
+}
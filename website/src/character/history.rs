@@ -0,0 +1,88 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+use super::storage;
+
+/// Move through the journal of edits asked for by the user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HistoryStep {
+    Undo,
+    Redo,
+
+    /// State after the given number of edits.
+    Restore(usize),
+}
+
+/// Journal of edits, with buttons to undo, redo or restore any earlier state.
+#[component]
+pub fn HistoryPanel(
+    journal: Signal<props::Journal>,
+    on_step: EventHandler<HistoryStep>,
+    error: Option<String>,
+) -> Element {
+    let mut expanded = use_signal(|| false);
+
+    let j = journal.read();
+    let applied = j.applied();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            div {
+                class: "flex gap-4 items-center",
+                span { class: "text-2xl", "History" }
+                button {
+                    disabled: !j.can_undo(),
+                    title: "Ctrl+Z",
+                    onclick: move |_| on_step(HistoryStep::Undo),
+                    "Undo"
+                }
+                button {
+                    disabled: !j.can_redo(),
+                    title: "Ctrl+Shift+Z or Ctrl+Y",
+                    onclick: move |_| on_step(HistoryStep::Redo),
+                    "Redo"
+                }
+                button {
+                    onclick: move |_| expanded.set(!expanded()),
+                    if expanded() { "Hide Edits" } else { "Show {j.edits().len()} Edits" }
+                }
+            }
+            if let Some(error) = error {
+                div { class: "text-red-700", "{error}" }
+            }
+            if expanded() {
+                ol {
+                    reversed: true,
+                    for (index, edit) in j.edits().iter().enumerate().rev() {
+                        li {
+                            key: "{index}",
+                            class: if index >= applied { "flex gap-4 items-start text-gray-500" } else { "flex gap-4 items-start" },
+                            span { "{storage::format_timestamp(edit.timestamp)}" }
+                            ul {
+                                for change in &edit.changes {
+                                    li { "{change}" }
+                                }
+                            }
+                            if index + 1 != applied {
+                                button {
+                                    title: "Restore the state after this edit",
+                                    onclick: move |_| on_step(HistoryStep::Restore(index + 1)),
+                                    "Restore"
+                                }
+                            }
+                        }
+                    }
+                    if applied > 0 {
+                        li {
+                            class: "flex gap-4",
+                            span { "Before the first recorded edit" }
+                            button { onclick: move |_| on_step(HistoryStep::Restore(0)), "Restore" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,281 @@
+use dioxus::prelude::*;
+
+use crate::{props, Route};
+
+use super::{
+    compare::ChangesTable,
+    storage::{self, IndexEntry},
+    validation::ValidationReport,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum ImportAction {
+    /// Store under a new id, keeping any character with the same name.
+    Add,
+
+    /// Overwrite the stored character with the same name.
+    Replace,
+    Skip,
+}
+
+/// Uploaded file waiting for the user to confirm the import.
+#[derive(Debug, Clone, PartialEq)]
+struct ImportCandidate {
+    file_name: String,
+    character: Result<props::Character, String>,
+
+    /// Stored character with the same name.
+    duplicate: Option<IndexEntry>,
+
+    /// Whether the character has validation errors, which need a confirmation to store it.
+    invalid: bool,
+
+    /// Whether the user chose to import the character despite its errors.
+    confirmed: bool,
+    action: ImportAction,
+}
+
+impl ImportCandidate {
+    fn new(
+        file_name: String,
+        character: Result<props::Character, String>,
+        index: &[IndexEntry],
+    ) -> Self {
+        let duplicate = character.as_ref().ok().and_then(|character| {
+            index
+                .iter()
+                .find(|entry| entry.name == character.name)
+                .cloned()
+        });
+        let invalid = character.as_ref().is_ok_and(|character| {
+            character
+                .validate()
+                .iter()
+                .any(|issue| issue.severity == props::Severity::Error)
+        });
+        let action = if character.is_ok() && !invalid {
+            ImportAction::Add
+        } else {
+            ImportAction::Skip
+        };
+
+        Self {
+            file_name,
+            character,
+            duplicate,
+            invalid,
+            confirmed: false,
+            action,
+        }
+    }
+
+    /// Whether the character can be stored, once any errors were confirmed.
+    fn importable(&self) -> bool {
+        self.character.is_ok()
+            && (!self.invalid || self.confirmed)
+            && self.action != ImportAction::Skip
+    }
+}
+
+/// Reads a character file, either JSON or the compact binary encoding.
+fn parse(bytes: &[u8]) -> Result<props::Character, props::SchemaError> {
+    if bytes.trim_ascii_start().starts_with(b"{") {
+        props::Character::from_json(&String::from_utf8_lossy(bytes))
+    } else {
+        props::Character::from_binary(bytes)
+    }
+}
+
+/// Upload of character files with a preview of each before they are stored.
+///
+/// A non-empty `share` code from a share link is offered for import right away.
+#[component]
+pub fn ImportPanel(index: Signal<Vec<IndexEntry>>, share: String) -> Element {
+    let mut candidates = use_signal(|| {
+        if share.is_empty() {
+            return Vec::new();
+        }
+        let character = props::Character::from_share_code(&share).map_err(|err| err.to_string());
+        vec![ImportCandidate::new(
+            "Shared link".to_owned(),
+            character,
+            &index.peek(),
+        )]
+    });
+    let mut message = use_signal(|| None::<String>);
+
+    let upload = move |e: Event<FormData>| {
+        message.set(None);
+        let Some(file_engine) = e.data.files() else {
+            message.set(Some(
+                "The browser did not provide the uploaded files".to_owned(),
+            ));
+            return;
+        };
+
+        spawn(async move {
+            let mut uploaded = Vec::new();
+            for file_name in file_engine.files() {
+                let character = match file_engine.read_file(&file_name).await {
+                    None => Err("Cannot read the file".to_owned()),
+                    Some(bytes) => parse(&bytes).map_err(|err| err.to_string()),
+                };
+                if let Err(err) = &character {
+                    tracing::error!("Failed to parse character file {file_name}: {err}");
+                }
+                uploaded.push(ImportCandidate::new(file_name, character, &index.read()));
+            }
+            candidates.set(uploaded);
+        });
+    };
+
+    let import = move |_| {
+        let mut imported = Vec::new();
+        for candidate in candidates.read().iter() {
+            let Ok(character) = &candidate.character else {
+                continue;
+            };
+            if !candidate.importable() {
+                continue;
+            }
+            let id = match (candidate.action, &candidate.duplicate) {
+                (ImportAction::Skip, _) => continue,
+                (ImportAction::Replace, Some(duplicate)) => duplicate.id.clone(),
+                (ImportAction::Add | ImportAction::Replace, _) => storage::new_id(),
+            };
+            storage::save_character(&id, character);
+            imported.push(id);
+        }
+        candidates.set(Vec::new());
+        index.set(storage::load_index());
+
+        match imported.as_slice() {
+            [id] => {
+                navigator().push(Route::Character { id: id.clone() });
+            }
+            _ => message.set(Some(format!("Imported {} characters", imported.len()))),
+        }
+    };
+
+    let importable = candidates
+        .read()
+        .iter()
+        .any(ImportCandidate::importable);
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            input {
+                r#type: "file",
+                title: "Upload",
+                name: "upload_character",
+                accept: "application/json,.json,.bin",
+                multiple: true,
+                oninput: upload,
+                "Upload Character"
+            }
+            for (position, candidate) in candidates.read().iter().enumerate() {
+                div {
+                    key: "{position}",
+                    class: "flex flex-col gap-1 border p-2",
+                    span { class: "text-xl", "{candidate.file_name}" }
+                    match &candidate.character {
+                        Err(error) => rsx! {
+                            div { class: "text-red-700", "{error}" }
+                        },
+                        Ok(character) => rsx! {
+                            CandidatePreview { character: character.clone() }
+                            if let Some(Ok(stored)) = candidate.duplicate.as_ref().and_then(|duplicate| storage::load_character(&duplicate.id)) {
+                                details {
+                                    summary { "Changes from the stored character" }
+                                    ChangesTable {
+                                        old: stored,
+                                        new: character.clone(),
+                                        old_title: "Stored",
+                                        new_title: "{candidate.file_name}",
+                                    }
+                                }
+                            }
+                            if candidate.invalid {
+                                label {
+                                    class: "flex gap-2 items-center text-red-700",
+                                    input {
+                                        r#type: "checkbox",
+                                        checked: candidate.confirmed,
+                                        onchange: move |e| {
+                                            let confirmed = e.checked();
+                                            let mut candidates = candidates.write();
+                                            let candidate = &mut candidates[position];
+                                            candidate.confirmed = confirmed;
+                                            candidate.action = if confirmed {
+                                                ImportAction::Add
+                                            } else {
+                                                ImportAction::Skip
+                                            };
+                                        },
+                                    }
+                                    "Import anyway, with the errors above, to fix them in the editor"
+                                }
+                            }
+                            if !candidate.invalid || candidate.confirmed {
+                                div {
+                                    class: "flex gap-4 items-center",
+                                    if let Some(duplicate) = &candidate.duplicate {
+                                        span { class: "text-yellow-700", "A character named {duplicate.name} is already stored" }
+                                    }
+                                    select {
+                                        onchange: move |e| {
+                                            let action = match e.value().as_str() {
+                                                "Replace" => ImportAction::Replace,
+                                                "Skip" => ImportAction::Skip,
+                                                _ => ImportAction::Add,
+                                            };
+                                            candidates.write()[position].action = action;
+                                        },
+                                        if candidate.duplicate.is_some() {
+                                            option { value: "Add", selected: candidate.action == ImportAction::Add, "Keep Both" }
+                                            option { value: "Replace", selected: candidate.action == ImportAction::Replace, "Replace" }
+                                        } else {
+                                            option { value: "Add", selected: candidate.action == ImportAction::Add, "Import" }
+                                        }
+                                        option { value: "Skip", selected: candidate.action == ImportAction::Skip, "Skip" }
+                                    }
+                                }
+                            }
+                        },
+                    }
+                }
+            }
+            if !candidates.read().is_empty() {
+                div {
+                    class: "flex gap-4",
+                    button { disabled: !importable, onclick: import, "Import" }
+                    button { onclick: move |_| candidates.set(Vec::new()), "Cancel" }
+                }
+            }
+            if let Some(message) = message() {
+                div { "{message}" }
+            }
+        }
+    }
+}
+
+#[component]
+fn CandidatePreview(character: props::Character) -> Element {
+    let classes = character
+        .classes()
+        .map(|(class, level)| format!("{class:?} {level}"))
+        .collect::<Vec<_>>()
+        .join(" / ");
+
+    rsx! {
+        div {
+            class: "flex gap-4",
+            span { class: "font-bold", "{character.name}" }
+            span { "Level {character.total_level()}" }
+            span { "{classes}" }
+            span { "{character.max_hit_points()} HP" }
+        }
+        ValidationReport { issues: character.validate() }
+    }
+}
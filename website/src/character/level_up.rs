@@ -0,0 +1,182 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+use super::feats::FeatPicker;
+
+#[component]
+pub fn LevelUpPanel(character: Signal<props::Character>) -> Element {
+    let mut class = use_signal(|| props::Class::Fighter);
+    let mut roll_hit_points = use_signal(|| false);
+    let feat = use_signal(|| props::ChosenFeat {
+        name: props::ABILITY_SCORE_IMPROVEMENT.to_owned(),
+        choices: vec![props::FeatChoice::Ability(props::Ability::Strength); 2],
+    });
+    let fighting_style = use_signal(|| props::ChosenFeat::new(""));
+    let mut subclass = use_signal(String::new);
+    let mut spells = use_signal(String::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let class_level = c.class_level(class()) + 1;
+    let prompts = c.level_up_prompts(class());
+    let features = class().features(class_level);
+    let multiclass = c.check_multiclass(class()).err().map(|err| err.to_string());
+
+    let level_up = move |_| {
+        let mut choices = Vec::new();
+        for prompt in character.read().level_up_prompts(class()) {
+            match prompt {
+                props::LevelUpPrompt::AbilityScoreImprovementOrFeat => {
+                    choices.push(props::LevelUpChoice::Feat(feat()));
+                }
+                props::LevelUpPrompt::FightingStyle => {
+                    choices.push(props::LevelUpChoice::FightingStyle(fighting_style()));
+                }
+                props::LevelUpPrompt::Subclass => {
+                    choices.push(props::LevelUpChoice::Subclass(
+                        subclass.read().trim().to_owned(),
+                    ));
+                }
+                props::LevelUpPrompt::Spells => {
+                    let spells = spells
+                        .read()
+                        .split(',')
+                        .map(str::trim)
+                        .filter(|spell| !spell.is_empty())
+                        .map(str::to_owned)
+                        .collect::<Vec<_>>();
+                    if !spells.is_empty() {
+                        choices.push(props::LevelUpChoice::Spells(spells));
+                    }
+                }
+            }
+        }
+
+        let hit_points = if roll_hit_points() {
+            props::HitPointsGain::Rolled(class().hit_die().roll())
+        } else {
+            props::HitPointsGain::Average
+        };
+
+        let result = character
+            .write()
+            .level_up(props::LevelUp {
+                class: class(),
+                hit_points,
+                choices,
+            })
+            .map(|_| ());
+
+        match result {
+            Ok(()) => {
+                error.set(None);
+                subclass.set(String::new());
+                spells.set(String::new());
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Level Up" }
+            div {
+                class: "flex gap-4 items-center",
+                select {
+                    onchange: move |e| {
+                        if let Ok(value) = e.parsed() {
+                            class.set(value);
+                        }
+                    },
+                    for option in props::Class::ALL {
+                        option { value: "{option:?}", selected: option == class(), "{option:?}" }
+                    }
+                }
+                span {
+                    "Level {class_level}, hit die {class().hit_die():?}, {c.max_hit_points()} max HP"
+                    if let Some(subclass) = c.subclass(class()) {
+                        ", {subclass}"
+                    }
+                }
+                label {
+                    input {
+                        r#type: "checkbox",
+                        checked: roll_hit_points(),
+                        onchange: move |e| roll_hit_points.set(e.checked()),
+                    }
+                    " Roll hit points"
+                }
+            }
+            if let Some(multiclass) = multiclass {
+                div { class: "text-red-700", "{multiclass}" }
+            }
+            if !features.is_empty() {
+                div { "New features: {features.join(\", \")}" }
+            }
+            for prompt in prompts {
+                match prompt {
+                    props::LevelUpPrompt::AbilityScoreImprovementOrFeat => rsx! {
+                        FeatPicker {
+                            feat,
+                            categories: vec![
+                                props::FeatCategory::Origin,
+                                props::FeatCategory::General,
+                                props::FeatCategory::FightingStyle,
+                                props::FeatCategory::EpicBoon,
+                            ],
+                        }
+                    },
+                    props::LevelUpPrompt::FightingStyle => rsx! {
+                        FeatPicker {
+                            feat: fighting_style,
+                            categories: vec![props::FeatCategory::FightingStyle],
+                        }
+                    },
+                    props::LevelUpPrompt::Subclass => rsx! {
+                        input {
+                            r#type: "text",
+                            placeholder: "Subclass",
+                            value: "{subclass}",
+                            oninput: move |e| subclass.set(e.value()),
+                        }
+                    },
+                    props::LevelUpPrompt::Spells => rsx! {
+                        input {
+                            r#type: "text",
+                            placeholder: "New spells, comma separated",
+                            value: "{spells}",
+                            oninput: move |e| spells.set(e.value()),
+                        }
+                    },
+                }
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+            div {
+                class: "flex gap-4",
+                button { onclick: level_up, "Level Up" }
+                button {
+                    disabled: c.levels().is_empty(),
+                    onclick: move |_| {
+                        character.write().revert_level_up();
+                        error.set(None);
+                    },
+                    "Revert Last Level"
+                }
+            }
+            ol {
+                for level in c.levels() {
+                    li {
+                        "{level.class:?} {level.class_level}: +{level.hit_points} HP"
+                        for choice in &level.choices {
+                            ", {choice}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
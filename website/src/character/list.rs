@@ -0,0 +1,224 @@
+use std::cmp::Ordering;
+
+use dioxus::prelude::*;
+
+use crate::{props, Route};
+
+use super::storage::{self, IndexEntry};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum SortColumn {
+    Name,
+    Species,
+    Classes,
+    Level,
+    Modified,
+}
+
+impl SortColumn {
+    const ALL: [SortColumn; 5] = [
+        SortColumn::Name,
+        SortColumn::Species,
+        SortColumn::Classes,
+        SortColumn::Level,
+        SortColumn::Modified,
+    ];
+
+    fn compare(self, a: &IndexEntry, b: &IndexEntry) -> Ordering {
+        match self {
+            SortColumn::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortColumn::Species => format!("{:?}", a.species).cmp(&format!("{:?}", b.species)),
+            SortColumn::Classes => a.classes_text().cmp(&b.classes_text()),
+            SortColumn::Level => a.level.cmp(&b.level),
+            SortColumn::Modified => a.modified.cmp(&b.modified),
+        }
+    }
+}
+
+fn matches_filter(entry: &IndexEntry, filter: &str) -> bool {
+    let filter = filter.trim().to_lowercase();
+    filter.is_empty()
+        || entry.name.to_lowercase().contains(&filter)
+        || format!("{:?}", entry.species)
+            .to_lowercase()
+            .contains(&filter)
+        || entry.classes_text().to_lowercase().contains(&filter)
+}
+
+fn load(entry: &IndexEntry) -> Result<props::Character, String> {
+    match storage::load_character(&entry.id) {
+        Some(Ok(character)) => Ok(character),
+        Some(Err(err)) => Err(format!("Cannot load {}: {err}", entry.name)),
+        None => Err(format!("{} is missing from storage", entry.name)),
+    }
+}
+
+/// Stored characters with the actions to manage them.
+#[component]
+pub fn CharacterTable(index: Signal<Vec<IndexEntry>>) -> Element {
+    let mut filter = use_signal(String::new);
+    let mut sort = use_signal(|| (SortColumn::Modified, true));
+    let mut renaming = use_signal(|| None::<(String, String)>);
+    let mut deleting = use_signal(|| None::<String>);
+    let mut error = use_signal(|| None::<String>);
+
+    let (sort_column, descending) = sort();
+    let mut entries = index
+        .read()
+        .iter()
+        .filter(|entry| matches_filter(entry, &filter.read()))
+        .cloned()
+        .collect::<Vec<_>>();
+    entries.sort_by(|a, b| {
+        let ordering = sort_column.compare(a, b);
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+
+    let mut duplicate = move |entry: &IndexEntry| match load(entry) {
+        Ok(mut character) => {
+            character.name = format!("{} (copy)", character.name);
+            storage::save_character(&storage::new_id(), &character);
+            index.set(storage::load_index());
+            error.set(None);
+        }
+        Err(err) => error.set(Some(err)),
+    };
+
+    let mut rename = move |entry: &IndexEntry, name: String| match load(entry) {
+        Ok(mut character) => {
+            character.name = name;
+            storage::save_character(&entry.id, &character);
+            index.set(storage::load_index());
+            renaming.set(None);
+            error.set(None);
+        }
+        Err(err) => error.set(Some(err)),
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            input {
+                r#type: "search",
+                placeholder: "Filter by name, species or class",
+                value: "{filter}",
+                oninput: move |e| filter.set(e.value()),
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+            table {
+                class: "table-auto text-left",
+                thead {
+                    tr {
+                        for column in SortColumn::ALL {
+                            th {
+                                button {
+                                    onclick: move |_| {
+                                        let (current, descending) = sort();
+                                        sort.set((column, current == column && !descending));
+                                    },
+                                    "{column:?}"
+                                    if column == sort_column {
+                                        if descending { " ▼" } else { " ▲" }
+                                    }
+                                }
+                            }
+                        }
+                        th { "Actions" }
+                    }
+                }
+                tbody {
+                    for entry in entries {
+                        tr {
+                            key: "{entry.id}",
+                            td {
+                                match renaming() {
+                                    Some((id, name)) if id == entry.id => rsx! {
+                                        input {
+                                            r#type: "text",
+                                            value: "{name}",
+                                            oninput: move |e| renaming.set(Some((id.clone(), e.value()))),
+                                        }
+                                    },
+                                    _ => rsx! {
+                                        Link { to: Route::Character { id: entry.id.clone() }, "{entry.name}" }
+                                    },
+                                }
+                            }
+                            td { "{entry.species:?}" }
+                            td { "{entry.classes_text()}" }
+                            td { "{entry.level}" }
+                            td { "{storage::format_timestamp(entry.modified)}" }
+                            td {
+                                class: "flex gap-2",
+                                if renaming().is_some_and(|(id, _)| id == entry.id) {
+                                    button {
+                                        onclick: {
+                                            let entry = entry.clone();
+                                            move |_| {
+                                                if let Some((_, name)) = renaming() {
+                                                    rename(&entry, name.trim().to_owned());
+                                                }
+                                            }
+                                        },
+                                        "Save"
+                                    }
+                                    button { onclick: move |_| renaming.set(None), "Cancel" }
+                                } else if deleting().as_ref() == Some(&entry.id) {
+                                    span { class: "text-red-700", "Delete {entry.name}?" }
+                                    button {
+                                        onclick: {
+                                            let id = entry.id.clone();
+                                            move |_| {
+                                                storage::delete_character(&id);
+                                                index.set(storage::load_index());
+                                                deleting.set(None);
+                                            }
+                                        },
+                                        "Delete"
+                                    }
+                                    button { onclick: move |_| deleting.set(None), "Cancel" }
+                                } else {
+                                    Link { to: Route::Character { id: entry.id.clone() }, "Open" }
+                                    Link {
+                                        to: Route::Compare { left: entry.id.clone(), right: String::new() },
+                                        "Compare"
+                                    }
+                                    button {
+                                        onclick: {
+                                            let entry = entry.clone();
+                                            move |_| duplicate(&entry)
+                                        },
+                                        "Duplicate"
+                                    }
+                                    button {
+                                        onclick: {
+                                            let rename = (entry.id.clone(), entry.name.clone());
+                                            move |_| renaming.set(Some(rename.clone()))
+                                        },
+                                        "Rename"
+                                    }
+                                    button {
+                                        onclick: {
+                                            let id = entry.id.clone();
+                                            move |_| deleting.set(Some(id.clone()))
+                                        },
+                                        "Delete"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            if index.read().is_empty() {
+                div { "No stored characters" }
+            }
+        }
+    }
+}
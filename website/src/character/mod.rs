@@ -0,0 +1,191 @@
+use dioxus::prelude::*;
+use dioxus_sdk::storage::use_persistent;
+
+use crate::{props, Route};
+
+mod background;
+mod compare;
+mod conditions;
+mod export;
+mod feats;
+mod history;
+mod import;
+mod level_up;
+mod list;
+mod resources;
+mod rest;
+mod sheet;
+mod stat_block;
+mod storage;
+mod validation;
+
+use self::{
+    background::BackgroundPanel,
+    conditions::ConditionsPanel,
+    export::ExportPanel,
+    feats::{FeatsPanel, StatsPanel},
+    history::{HistoryPanel, HistoryStep},
+    import::ImportPanel,
+    level_up::LevelUpPanel,
+    list::CharacterTable,
+    resources::ResourcesPanel,
+    rest::RestPanel,
+    sheet::SheetEditor,
+    validation::ValidationReport,
+};
+
+pub use self::{compare::Compare, stat_block::ArticleStatBlock};
+
+#[component]
+pub fn Character(id: String) -> Element {
+    // Keyed by the id, so that switching characters starts from a fresh state.
+    rsx! {
+        CharacterPage { key: "{id}", id }
+    }
+}
+
+#[component]
+fn CharacterPage(id: String) -> Element {
+    // Stored as a versioned JSON document, so older characters are upgraded on load.
+    let mut stored = use_persistent(id.clone(), String::new);
+    // A document that fails to load is kept in storage until the user discards it.
+    let (mut character, mut load_error) = use_hook(|| {
+        let stored = stored.peek();
+        let (character, error) = if stored.is_empty() {
+            (props::Character::new(), None)
+        } else {
+            // Characters stored before versioning were not JSON documents.
+            let legacy = || storage::load_legacy_character(&id).and_then(Result::ok);
+            match props::Character::from_json(&stored).or_else(|err| legacy().ok_or(err)) {
+                Ok(character) => (character, None),
+                Err(err) => {
+                    tracing::error!("Failed to load character: {err}");
+                    (props::Character::new(), Some(err.to_string()))
+                }
+            }
+        };
+        (Signal::new(character), Signal::new(error))
+    });
+    let mut journal = use_signal(|| storage::load_journal(&id));
+    let mut history_error = use_signal(|| None::<String>);
+
+    let journal_id = id.clone();
+    use_effect(move || {
+        if load_error.read().is_some() {
+            return;
+        }
+        let character = character.read();
+        let document = character.to_json_value();
+        let json = document.to_string();
+        let modified = *stored.peek() != json;
+        if modified {
+            let previous = serde_json::from_str::<serde_json::Value>(&stored.peek());
+            if let Ok(previous) = previous {
+                if journal.write().record(&previous, &document, storage::now()) {
+                    storage::save_journal(&id, &journal.peek());
+                }
+            }
+            stored.set(json);
+        }
+        storage::update_index(&id, &character, modified);
+    });
+
+    // Stores the restored state before showing it, so that it is not recorded as a new edit.
+    let step = use_callback(move |step: HistoryStep| {
+        let restored = match step {
+            HistoryStep::Undo => journal.write().undo(&character.peek()),
+            HistoryStep::Redo => journal.write().redo(&character.peek()),
+            HistoryStep::Restore(applied) => journal.write().restore(applied, &character.peek()),
+        };
+        match restored {
+            Ok(restored) => {
+                stored.set(restored.to_json());
+                storage::save_journal(&journal_id, &journal.peek());
+                storage::update_index(&journal_id, &restored, true);
+                character.set(restored);
+                history_error.set(None);
+            }
+            Err(err) => history_error.set(Some(err.to_string())),
+        }
+    });
+
+    let shortcuts = move |e: KeyboardEvent| {
+        let modifiers = e.modifiers();
+        if !(modifiers.ctrl() || modifiers.meta()) {
+            return;
+        }
+        let redo = match e.key() {
+            Key::Character(key) if key.eq_ignore_ascii_case("z") => modifiers.shift(),
+            Key::Character(key) if key.eq_ignore_ascii_case("y") => true,
+            _ => return,
+        };
+        e.prevent_default();
+
+        let (can_undo, can_redo) = {
+            let journal = journal.peek();
+            (journal.can_undo(), journal.can_redo())
+        };
+        if redo && can_redo {
+            step(HistoryStep::Redo);
+        } else if !redo && can_undo {
+            step(HistoryStep::Undo);
+        }
+    };
+
+    if let Some(error) = load_error() {
+        return rsx! {
+            div {
+                class: "flex flex-col gap-2 py-4",
+                span { class: "text-3xl", "Character" }
+                div { class: "text-red-700", "{error}" }
+                button {
+                    title: "Replace the stored data with a new character",
+                    onclick: move |_| load_error.set(None),
+                    "Discard and Start Over"
+                }
+            }
+        };
+    }
+
+    let c = character.read();
+
+    rsx! {
+        div {
+            class: "flex flex-col",
+            tabindex: 0,
+            onkeydown: shortcuts,
+            span { class: "text-3xl", "Character" }
+            SheetEditor { character }
+            HistoryPanel { journal, on_step: step, error: history_error() }
+            ValidationReport { issues: c.validate() }
+            ExportPanel { character }
+            StatsPanel { character }
+            BackgroundPanel { character }
+            RestPanel { character }
+            ConditionsPanel { character }
+            ResourcesPanel { character }
+            FeatsPanel { character }
+            LevelUpPanel { character }
+        }
+    }
+}
+
+#[component]
+pub fn CharactersList(share: String) -> Element {
+    let index = use_signal(storage::load_index);
+
+    let create_character = || {
+        navigator().push(Route::Character {
+            id: storage::new_id(),
+        });
+    };
+
+    rsx! {
+        div {
+            h1 { "Characters List" }
+            button { onclick: move |_| create_character(), title: "Create new character to edit", name: "new_character", "New Character" }
+            ImportPanel { key: "{share}", index, share: share.clone() }
+            CharacterTable { index }
+        }
+    }
+}
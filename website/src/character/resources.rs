@@ -0,0 +1,95 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Pools larger than this are tracked with a number instead of checkboxes.
+const MAX_CHECKBOXES: u32 = 20;
+
+/// Limited-use class features with a checkbox per use.
+#[component]
+pub fn ResourcesPanel(character: Signal<props::Character>) -> Element {
+    let mut amount = use_signal(|| 1);
+    let mut error = use_signal(|| None::<String>);
+    let mut recovered = use_signal(Vec::<props::RecoveredResource>::new);
+
+    let resources = character.read().resources();
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Resources" }
+            for state in resources {
+                div {
+                    class: "flex flex-wrap gap-2 items-center",
+                    span { "{state.resource.name} ({state.resource.class:?})" }
+                    if state.maximum <= MAX_CHECKBOXES {
+                        for index in 0..state.maximum {
+                            input {
+                                r#type: "checkbox",
+                                title: "Spent",
+                                checked: index < state.spent,
+                                onchange: move |e| {
+                                    let spent = if e.checked() { index + 1 } else { index };
+                                    let result = character.write().set_resource_spent(
+                                        state.resource.class,
+                                        state.resource.name,
+                                        spent,
+                                    );
+                                    error.set(result.err().map(|err| err.to_string()));
+                                },
+                            }
+                        }
+                    } else {
+                        span { "{state.available()}/{state.maximum}" }
+                        input {
+                            r#type: "number",
+                            min: 1,
+                            value: "{amount}",
+                            onchange: move |e| {
+                                if let Ok(value) = e.parsed() {
+                                    amount.set(value);
+                                }
+                            },
+                        }
+                        button {
+                            onclick: move |_| {
+                                let result = character.write().spend_resource(
+                                    state.resource.class,
+                                    state.resource.name,
+                                    amount(),
+                                );
+                                error.set(result.err().map(|err| err.to_string()));
+                            },
+                            "Spend"
+                        }
+                        button {
+                            onclick: move |_| {
+                                let result = character.write().set_resource_spent(
+                                    state.resource.class,
+                                    state.resource.name,
+                                    0,
+                                );
+                                error.set(result.err().map(|err| err.to_string()));
+                            },
+                            "Reset"
+                        }
+                    }
+                    span { class: "text-gray-500", "{state.recovery}" }
+                }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                button {
+                    onclick: move |_| recovered.set(character.write().recover_resources(props::Recovery::Dawn)),
+                    "Dawn"
+                }
+                for resource in recovered() {
+                    span { "{resource}" }
+                }
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+        }
+    }
+}
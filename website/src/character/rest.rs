@@ -0,0 +1,153 @@
+use std::collections::HashMap;
+
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Hit points, hit dice, spell slots and rests.
+#[component]
+pub fn RestPanel(character: Signal<props::Character>) -> Element {
+    let mut amount = use_signal(|| 1);
+    let mut hit_dice = use_signal(HashMap::<props::Class, u32>::new);
+    let mut summaries = use_signal(Vec::<props::RestSummary>::new);
+    let mut error = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let classes = c.classes().collect::<Vec<_>>();
+    let spell_slots = c.spell_slots();
+    let spell_slots_spent = c.spell_slots_spent();
+    let pact_slots = c.pact_slots();
+
+    let short_rest = move |_| {
+        let rolls = hit_dice
+            .read()
+            .iter()
+            .flat_map(|(&class, &count)| {
+                (0..count).map(move |_| props::HitDieRoll {
+                    class,
+                    roll: class.hit_die().roll(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        match character.write().short_rest(&rolls) {
+            Ok(summary) => {
+                summaries.write().push(summary);
+                hit_dice.write().clear();
+                error.set(None);
+            }
+            Err(err) => error.set(Some(err.to_string())),
+        }
+    };
+
+    let long_rest = move |_| {
+        let summary = character.write().long_rest();
+        summaries.write().push(summary);
+        error.set(None);
+    };
+
+    let undo = move |_| {
+        if let Some(summary) = summaries.write().pop() {
+            character.write().undo_rest(&summary);
+        }
+    };
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            span { class: "text-2xl", "Rest" }
+            div {
+                class: "flex gap-4 items-center",
+                span { "HP {c.hit_points()}/{c.max_hit_points()}" }
+                input {
+                    r#type: "number",
+                    min: 1,
+                    value: "{amount}",
+                    onchange: move |e| {
+                        if let Ok(value) = e.parsed() {
+                            amount.set(value);
+                        }
+                    },
+                }
+                button { onclick: move |_| character.write().take_damage(amount()), "Damage" }
+                button { onclick: move |_| character.write().heal(amount()), "Heal" }
+                span { "Exhaustion {c.exhaustion()}" }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                for (class, level) in classes {
+                    label {
+                        "{class:?} {class.hit_die():?}: {c.hit_dice(class)}/{level}, spend "
+                        input {
+                            r#type: "number",
+                            min: 0,
+                            max: c.hit_dice(class),
+                            value: hit_dice.read().get(&class).copied().unwrap_or(0),
+                            onchange: move |e| {
+                                if let Ok(value) = e.parsed() {
+                                    hit_dice.write().insert(class, value);
+                                }
+                            },
+                        }
+                    }
+                }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                for (index, (slots, spent)) in spell_slots.into_iter().zip(spell_slots_spent).enumerate().filter(|(_, (slots, _))| *slots > 0) {
+                    div {
+                        "Level {index + 1}"
+                        for slot in 0..slots {
+                            input {
+                                r#type: "checkbox",
+                                checked: slot < spent,
+                                onchange: move |e| {
+                                    let level = index as u32 + 1;
+                                    let result = if e.checked() {
+                                        character.write().spend_spell_slot(level)
+                                    } else {
+                                        character.write().restore_spell_slot(level)
+                                    };
+                                    error.set(result.err().map(|err| err.to_string()));
+                                },
+                            }
+                        }
+                    }
+                }
+                if pact_slots.count > 0 {
+                    div {
+                        "Pact level {pact_slots.level}"
+                        for slot in 0..pact_slots.count {
+                            input {
+                                r#type: "checkbox",
+                                checked: slot < c.pact_slots_spent(),
+                                onchange: move |e| {
+                                    if e.checked() {
+                                        let result = character.write().spend_pact_slot();
+                                        error.set(result.err().map(|err| err.to_string()));
+                                    } else {
+                                        character.write().restore_pact_slot();
+                                    }
+                                },
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(error) = error() {
+                div { class: "text-red-700", "{error}" }
+            }
+            div {
+                class: "flex gap-4",
+                button { onclick: short_rest, "Short Rest" }
+                button { onclick: long_rest, "Long Rest" }
+                button { disabled: summaries.read().is_empty(), onclick: undo, "Undo Rest" }
+            }
+            ol {
+                for summary in summaries() {
+                    li { "{summary}" }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Editor of the identity and base ability scores of the character.
+#[component]
+pub fn SheetEditor(character: Signal<props::Character>) -> Element {
+    let mut score_error = use_signal(|| None::<String>);
+
+    let c = character.read();
+    let (order, morality) = c.alignment;
+    let classes = c
+        .classes()
+        .map(|(class, level)| match c.subclass(class) {
+            Some(subclass) => format!("{class:?} {level} ({subclass})"),
+            None => format!("{class:?} {level}"),
+        })
+        .collect::<Vec<_>>();
+    let last_level = c.levels().last().map(|level| level.class);
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2 py-4",
+            div {
+                class: "flex gap-4 items-center",
+                input {
+                    class: "text-5xl",
+                    r#type: "text",
+                    placeholder: "Name",
+                    value: "{c.name}",
+                    oninput: move |e| character.write().name = e.value(),
+                }
+                span { class: "text-5xl", "{c.total_level()}" }
+            }
+            div {
+                class: "grid grid-cols-2 gap-2 items-center max-w-xl",
+                label { "Species" }
+                select {
+                    onchange: move |e| {
+                        if let Ok(value) = e.parsed() {
+                            character.write().species = value;
+                        }
+                    },
+                    for option in props::Species::ALL {
+                        option { value: "{option:?}", selected: option == c.species, "{option:?}" }
+                    }
+                }
+                label { "Alignment" }
+                div {
+                    class: "flex gap-2",
+                    select {
+                        onchange: move |e| {
+                            if let Ok(value) = e.parsed() {
+                                character.write().alignment.0 = value;
+                            }
+                        },
+                        for option in props::Order::ALL {
+                            option { value: "{option:?}", selected: option == order, "{option:?}" }
+                        }
+                    }
+                    select {
+                        onchange: move |e| {
+                            if let Ok(value) = e.parsed() {
+                                character.write().alignment.1 = value;
+                            }
+                        },
+                        for option in props::Morality::ALL {
+                            option { value: "{option:?}", selected: option == morality, "{option:?}" }
+                        }
+                    }
+                }
+                label { "Origin" }
+                input {
+                    r#type: "text",
+                    placeholder: "Origin",
+                    value: "{c.origin}",
+                    oninput: move |e| character.write().origin = e.value(),
+                }
+            }
+            div {
+                class: "grid grid-cols-6 gap-2 max-w-xl",
+                for ability in props::Ability::ALL {
+                    label {
+                        class: "flex flex-col items-center",
+                        span { "{ability.abbreviation()}" }
+                        input {
+                            class: "w-16 text-center",
+                            r#type: "number",
+                            min: props::ABILITY_SCORE_RANGE.0,
+                            max: props::ABILITY_SCORE_RANGE.1,
+                            value: "{c.base_ability_score(ability)}",
+                            onchange: move |e| {
+                                let result = match e.value().trim().parse() {
+                                    Ok(score) => character
+                                        .write()
+                                        .set_base_ability_score(ability, score)
+                                        .map_err(|err| err.to_string()),
+                                    Err(_) => Err(format!("{ability:?} must be a number")),
+                                };
+                                score_error.set(result.err());
+                            },
+                        }
+                    }
+                }
+            }
+            if let Some(error) = score_error() {
+                div { class: "text-red-700", "{error}" }
+            }
+            div {
+                class: "flex gap-4 items-center",
+                span { class: "text-xl", "Classes" }
+                if classes.is_empty() {
+                    span { "No levels yet, use Level Up below" }
+                }
+                for class in classes {
+                    span { "{class}" }
+                }
+                if let Some(class) = last_level {
+                    button {
+                        title: "Undo the last level-up",
+                        onclick: move |_| {
+                            character.write().revert_level_up();
+                        },
+                        "Remove Last Level ({class:?})"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+use dioxus::prelude::*;
+use dioxus_sdk::storage::use_persistent;
+
+use crate::{props, Route};
+
+/// Stat block of the character an article is bound to by its front matter.
+///
+/// A stored character is preferred, so that the block follows the edits made on its
+/// page; the embedded share code is shown when the character is not stored here.
+#[component]
+pub fn ArticleStatBlock(id: Option<String>, code: Option<String>) -> Element {
+    match (id, code) {
+        (Some(id), code) => rsx! {
+            StoredStatBlock { key: "{id}", id, code }
+        },
+        (None, Some(code)) => rsx! {
+            EmbeddedStatBlock { code }
+        },
+        (None, None) => rsx! {},
+    }
+}
+
+#[component]
+fn StoredStatBlock(id: String, code: Option<String>) -> Element {
+    // Same storage as the character page, read as the page writes it.
+    let stored = use_persistent(id.clone(), String::new);
+
+    let stored = stored.read();
+    if stored.is_empty() {
+        return match code {
+            Some(code) => rsx! {
+                EmbeddedStatBlock { code }
+            },
+            None => rsx! {
+                StatBlockError { error: "Character {id} is not stored in this browser" }
+            },
+        };
+    }
+
+    match props::Character::from_json(&stored) {
+        Ok(character) => rsx! {
+            StatBlock { character, id }
+        },
+        Err(err) => rsx! {
+            StatBlockError { error: "{err}" }
+        },
+    }
+}
+
+/// Character embedded in an article by its share code.
+fn embedded_character(code: &str) -> Result<props::Character, props::SchemaError> {
+    props::Character::from_share_code(code.trim())
+}
+
+#[component]
+fn EmbeddedStatBlock(code: String) -> Element {
+    match embedded_character(&code) {
+        Ok(character) => rsx! {
+            StatBlock { character }
+        },
+        Err(err) => rsx! {
+            StatBlockError { error: "{err}" }
+        },
+    }
+}
+
+#[component]
+fn StatBlockError(error: String) -> Element {
+    rsx! {
+        aside {
+            class: "flex flex-col gap-2 p-4 w-72 border-2 border-red-700 text-red-700",
+            "{error}"
+        }
+    }
+}
+
+/// Compact summary of a character, linking to its sheet when it is stored.
+#[component]
+pub fn StatBlock(character: props::Character, id: Option<String>) -> Element {
+    let classes = character
+        .classes()
+        .map(|(class, level)| format!("{class:?} {level}"))
+        .collect::<Vec<_>>()
+        .join(" / ");
+    let armor_class = character
+        .armor_class_bonuses()
+        .iter()
+        .map(|bonus| bonus.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    rsx! {
+        aside {
+            class: "flex flex-col gap-2 p-4 w-72 shrink-0 border-2 border-amber-800 bg-amber-50",
+            match id {
+                Some(id) => rsx! {
+                    Link {
+                        class: "text-2xl hover:underline",
+                        to: Route::Character { id },
+                        "{character.name}"
+                    }
+                },
+                None => rsx! {
+                    span { class: "text-2xl", "{character.name}" }
+                },
+            }
+            span { "{character.species:?}, level {character.total_level()}" }
+            if !classes.is_empty() {
+                span { "{classes}" }
+            }
+            div {
+                class: "flex gap-4",
+                span { title: "{armor_class}", "AC {character.armor_class()}" }
+                span { "HP {character.hit_points()} / {character.max_hit_points()}" }
+            }
+            div {
+                class: "grid grid-cols-6 gap-1 text-center",
+                for ability in props::Ability::ALL {
+                    div {
+                        class: "flex flex-col",
+                        span { class: "text-xs", "{ability.abbreviation()}" }
+                        span { "{character.ability_score(ability)}" }
+                        span { class: "text-sm", "({character.ability_modifier(ability):+})" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        articles,
+        lore::{front_matter, ArticleMeta, ARTICLE_OPTIONS},
+    };
+
+    #[test]
+    fn nitros_article_embeds_its_character() {
+        let article = articles::article("characters/Nitros").unwrap();
+        let (kind, text) = front_matter(article.source, ARTICLE_OPTIONS).unwrap();
+        let meta = ArticleMeta::parse(kind, &text).unwrap();
+        assert_eq!(meta, article.meta);
+        assert_eq!(meta.character, None);
+
+        let character = embedded_character(meta.character_code.as_deref().unwrap()).unwrap();
+        assert_eq!(character.name, "Нитрос");
+        assert_eq!(character.species, props::Species::Elf);
+        assert_eq!(
+            character.classes().collect::<Vec<_>>(),
+            [(props::Class::Artificer, 2), (props::Class::Warlock, 1)]
+        );
+        assert!(character.validate().is_empty());
+    }
+}
@@ -0,0 +1,256 @@
+use std::fmt;
+
+use dioxus_sdk::storage::{SessionStorage, StorageBacking};
+use serde::de::{Deserialize, Deserializer, SeqAccess, Visitor};
+
+use crate::props;
+
+/// Storage key of the list of stored characters.
+const INDEX_KEY: &str = "characters";
+
+/// Stored character, as listed in the index.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct IndexEntry {
+    /// Storage key of the character document.
+    pub id: String,
+    pub name: String,
+    pub species: props::Species,
+    pub classes: Vec<(props::Class, u32)>,
+    pub level: u32,
+
+    /// Milliseconds since the Unix epoch of the last change.
+    pub modified: u64,
+}
+
+impl IndexEntry {
+    fn new(id: &str, character: &props::Character, modified: u64) -> Self {
+        IndexEntry {
+            id: id.to_owned(),
+            name: character.name.clone(),
+            species: character.species,
+            classes: character.classes().collect(),
+            level: character.total_level(),
+            modified,
+        }
+    }
+
+    pub fn classes_text(&self) -> String {
+        self.classes
+            .iter()
+            .map(|(class, level)| format!("{class:?} {level}"))
+            .collect::<Vec<_>>()
+            .join(" / ")
+    }
+}
+
+/// Characters in storage, in the order they were first saved.
+///
+/// Entries written by older versions of the index are rebuilt from the characters.
+pub fn load_index() -> Vec<IndexEntry> {
+    let Some(json) = SessionStorage::get::<String>(&INDEX_KEY.to_owned()) else {
+        return Vec::new();
+    };
+    let entries = serde_json::from_str::<Vec<serde_json::Value>>(&json).unwrap_or_else(|err| {
+        tracing::error!("Failed to load character index: {err}");
+        Vec::new()
+    });
+
+    entries
+        .into_iter()
+        .filter_map(|entry| {
+            let id = entry.get("id")?.as_str()?.to_owned();
+            serde_json::from_value(entry).ok().or_else(|| {
+                let character = load_character(&id)?.ok()?;
+                Some(IndexEntry::new(&id, &character, 0))
+            })
+        })
+        .collect()
+}
+
+fn save_index(index: &[IndexEntry]) {
+    let json = serde_json::to_string(index).expect("character index serializes to JSON");
+    SessionStorage::set(INDEX_KEY.to_owned(), &json);
+}
+
+/// Adds the character to the index or refreshes its entry.
+///
+/// The modification time is kept unless `modified` is set.
+pub fn update_index(id: &str, character: &props::Character, modified: bool) {
+    let mut index = load_index();
+    let existing = index.iter_mut().find(|entry| entry.id == id);
+    let time = match &existing {
+        Some(entry) if !modified => entry.modified,
+        _ => now(),
+    };
+    let entry = IndexEntry::new(id, character, time);
+    match existing {
+        Some(existing) if *existing == entry => return,
+        Some(existing) => *existing = entry,
+        None => index.push(entry),
+    }
+    save_index(&index);
+}
+
+/// Reads the character stored under the id, if there is one.
+///
+/// Characters stored before versioning are upgraded when they are not JSON documents.
+pub fn load_character(id: &str) -> Option<Result<props::Character, props::SchemaError>> {
+    let json = SessionStorage::get::<String>(&id.to_owned())?;
+    (!json.is_empty()).then(|| {
+        props::Character::from_json(&json)
+            .or_else(|err| load_legacy_character(id).and_then(Result::ok).ok_or(err))
+    })
+}
+
+/// Every byte of a value in storage, whatever its type.
+#[derive(Clone)]
+struct StoredBytes(Vec<u8>);
+
+impl<'de> Deserialize<'de> for StoredBytes {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct BytesVisitor;
+
+        impl<'de> Visitor<'de> for BytesVisitor {
+            type Value = StoredBytes;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("any bytes")
+            }
+
+            fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+            where
+                A: SeqAccess<'de>,
+            {
+                // The binary encoding has no length for the value, so the bytes are read
+                // until the input runs out.
+                let mut bytes = Vec::new();
+                while let Ok(Some(byte)) = seq.next_element() {
+                    bytes.push(byte);
+                }
+                Ok(StoredBytes(bytes))
+            }
+        }
+
+        deserializer.deserialize_tuple(usize::MAX, BytesVisitor)
+    }
+}
+
+/// Reads the character stored under the id before versioning, when the storage kept
+/// its binary encoding instead of a JSON document.
+pub fn load_legacy_character(id: &str) -> Option<Result<props::Character, props::SchemaError>> {
+    let StoredBytes(bytes) = SessionStorage::get(&id.to_owned())?;
+    Some(props::Character::from_legacy_binary(&bytes))
+}
+
+/// Writes the character under the id in the format read by the character page,
+/// recording the changes in its journal.
+pub fn save_character(id: &str, character: &props::Character) {
+    let document = character.to_json_value();
+    let stored = SessionStorage::get::<String>(&id.to_owned())
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok());
+    if let Some(stored) = stored {
+        let mut journal = load_journal(id);
+        if journal.record(&stored, &document, now()) {
+            save_journal(id, &journal);
+        }
+    }
+
+    SessionStorage::set(id.to_owned(), &document.to_string());
+    update_index(id, character, true);
+}
+
+/// Removes the character from the index and clears its document and journal.
+pub fn delete_character(id: &str) {
+    let mut index = load_index();
+    index.retain(|entry| entry.id != id);
+    save_index(&index);
+    SessionStorage::set(id.to_owned(), &String::new());
+    SessionStorage::set(journal_key(id), &String::new());
+}
+
+/// Journal of a character is stored next to it.
+fn journal_key(id: &str) -> String {
+    format!("{id}.journal")
+}
+
+pub fn load_journal(id: &str) -> props::Journal {
+    SessionStorage::get::<String>(&journal_key(id))
+        .filter(|json| !json.is_empty())
+        .and_then(|json| {
+            serde_json::from_str(&json)
+                .inspect_err(|err| tracing::error!("Failed to load journal of {id}: {err}"))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
+pub fn save_journal(id: &str, journal: &props::Journal) {
+    let json = serde_json::to_string(journal).expect("journal serializes to JSON");
+    SessionStorage::set(journal_key(id), &json);
+}
+
+pub fn new_id() -> String {
+    rand::random::<u128>().to_string()
+}
+
+/// Milliseconds since the Unix epoch.
+#[cfg(target_family = "wasm")]
+pub fn now() -> u64 {
+    js_sys::Date::now() as u64
+}
+
+#[cfg(not(target_family = "wasm"))]
+pub fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+/// Formats milliseconds since the Unix epoch as a UTC date and time.
+pub fn format_timestamp(millis: u64) -> String {
+    if millis == 0 {
+        return "unknown".to_owned();
+    }
+
+    let minutes = millis / 60_000;
+    let (days, minute_of_day) = (minutes / 1440, minutes % 1440);
+
+    // Civil date from days since 1970-01-01, counting eras of 400 years from 0000-03-01.
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        minute_of_day / 60,
+        minute_of_day % 60
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stored_bytes_are_read_to_the_end() {
+        let bytes = include_bytes!("../../tests/fixtures/character_v0.bin");
+        let StoredBytes(stored) = postcard::from_bytes(bytes).unwrap();
+
+        assert_eq!(stored, bytes);
+        assert!(props::Character::from_legacy_binary(&stored).is_ok());
+    }
+}
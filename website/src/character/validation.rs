@@ -0,0 +1,27 @@
+use dioxus::prelude::*;
+
+use crate::props;
+
+/// Problems found in a character, errors first.
+#[component]
+pub fn ValidationReport(issues: Vec<props::ValidationIssue>) -> Element {
+    if issues.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        ul {
+            class: "flex flex-col py-2",
+            for issue in issues {
+                li {
+                    class: match issue.severity {
+                        props::Severity::Error => "text-red-700",
+                        props::Severity::Warning => "text-yellow-700",
+                    },
+                    title: "{issue.path}",
+                    "{issue}"
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,252 @@
+//! Links of the articles: their routes, the broken ones and the backlinks.
+
+use std::fmt;
+
+use pulldown_cmark::{Event, LinkType, Parser, Tag};
+
+use crate::{
+    articles::{self, has_file},
+    lore::{self, outline, ArticleEntry, ArticleLink, ARTICLE_OPTIONS},
+    Route,
+};
+
+/// Path of an article linking to another one, with the sentences around its links.
+type Linking = (&'static str, &'static [&'static str]);
+
+/// Articles linking to each article, by the path of the linked one, sorted by title.
+static BACKLINKS: &[(&str, &[Linking])] = include!(concat!(env!("OUT_DIR"), "/backlinks.rs"));
+
+impl ArticleLink {
+    /// Resolves the destination of a link in the article at `article`,
+    /// a path such as `campaign/session_1`.
+    pub fn resolve(article: &str, destination: &str) -> Self {
+        let base_path = dioxus::cli_config::base_path();
+        lore::resolve(base_path.as_deref(), article, destination)
+    }
+}
+
+/// Route of the article at the path, scrolled to the section when it is not empty.
+pub fn article_route(path: &str, section: &str) -> Route {
+    Route::Article {
+        segments: path
+            .trim_matches('/')
+            .split('/')
+            .map(str::to_owned)
+            .collect(),
+        section: section.to_owned(),
+    }
+}
+
+/// Why a link leads nowhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BrokenTarget {
+    /// No article is at the path.
+    Article(String),
+
+    /// The article has no heading with the id.
+    Section { article: String, section: String },
+
+    /// No image or other file is at the path.
+    File(String),
+}
+
+impl fmt::Display for BrokenTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BrokenTarget::Article(path) => write!(f, "no article {path}"),
+            BrokenTarget::Section { article, section } => {
+                write!(f, "no section #{section} in {article}")
+            }
+            BrokenTarget::File(path) => write!(f, "no file {path}"),
+        }
+    }
+}
+
+/// Link, image or reference definition of an article that leads nowhere.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct BrokenLink {
+    /// Path of the article with the link, without the extension.
+    pub article: String,
+
+    /// Line of the link, counting from 1.
+    pub line: usize,
+    pub destination: String,
+    pub target: BrokenTarget,
+}
+
+impl fmt::Display for BrokenLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "resources/articles/{}.md:{}: broken link {}: {}",
+            self.article, self.line, self.destination, self.target
+        )
+    }
+}
+
+fn has_section(source: &str, section: &str) -> bool {
+    outline(source, ARTICLE_OPTIONS)
+        .iter()
+        .any(|entry| entry.id == section)
+}
+
+fn broken_target(
+    article: &str,
+    source: &str,
+    destination: &str,
+    image: bool,
+) -> Option<BrokenTarget> {
+    match ArticleLink::resolve(article, destination) {
+        ArticleLink::External(_) => None,
+        ArticleLink::Section(section) => {
+            (!has_section(source, &section)).then(|| BrokenTarget::Section {
+                article: article.to_owned(),
+                section,
+            })
+        }
+        ArticleLink::Article { path, .. } if image => {
+            (!has_file(&path)).then_some(BrokenTarget::File(path))
+        }
+        ArticleLink::Article { path, section } => match articles::article(&path) {
+            None => Some(BrokenTarget::Article(path)),
+            Some(target) if !section.is_empty() && !has_section(target.source, &section) => {
+                Some(BrokenTarget::Section {
+                    article: path,
+                    section,
+                })
+            }
+            Some(_) => None,
+        },
+    }
+}
+
+/// Links, images and reference definitions of the article at `article` that lead
+/// nowhere, in the order of their lines.
+///
+/// Links to other sites are not followed. Wiki links are not checked, as the ones
+/// to missing articles lead to their stubs on purpose.
+pub fn broken_links(article: &str, source: &str) -> Vec<BrokenLink> {
+    let parser = Parser::new_ext(source, ARTICLE_OPTIONS);
+
+    // Links by reference are checked at their definitions, used or not.
+    let mut links = parser
+        .reference_definitions()
+        .iter()
+        .map(|(_, definition)| (definition.dest.to_string(), definition.span.start, false))
+        .collect::<Vec<_>>();
+    for (event, range) in parser.into_offset_iter() {
+        match event {
+            Event::Start(Tag::Link {
+                link_type: LinkType::Inline,
+                dest_url,
+                ..
+            }) => links.push((dest_url.to_string(), range.start, false)),
+            Event::Start(Tag::Image {
+                link_type: LinkType::Inline,
+                dest_url,
+                ..
+            }) => links.push((dest_url.to_string(), range.start, true)),
+            _ => {}
+        }
+    }
+
+    let mut broken = links
+        .into_iter()
+        .filter_map(|(destination, offset, image)| {
+            let target = broken_target(article, source, &destination, image)?;
+            Some(BrokenLink {
+                article: article.to_owned(),
+                line: source[..offset].matches('\n').count() + 1,
+                destination,
+                target,
+            })
+        })
+        .collect::<Vec<_>>();
+    broken.sort_by_key(|link| link.line);
+    broken
+}
+
+/// Article that links to another one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backlink {
+    pub from: &'static ArticleEntry,
+
+    /// Sentences of the linking article around its links, each one once.
+    pub sentences: &'static [&'static str],
+}
+
+/// Articles of the tree that link to the article at the path, sorted by title.
+///
+/// Found by the build script; unlisted articles are left out, so that they are only
+/// reachable by their links.
+pub fn backlinks(path: &str) -> Vec<Backlink> {
+    let Some((_, links)) = BACKLINKS.iter().find(|(target, _)| *target == path) else {
+        return Vec::new();
+    };
+    links
+        .iter()
+        .filter_map(|&(from, sentences)| {
+            Some(Backlink {
+                from: articles::article(from)?,
+                sentences,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broken_links_are_reported_with_lines() {
+        let source = "# Кампания\n\n\
+                      [Кзельчпакх] и [Нитрос](/characters/Nitros#предыстория).\n\n\
+                      [Дафна](../characters/Dafna.md#нет-такой) ![Карта](map.png) [Вверх](#кампания)\n\n\
+                      [Кзельчпакх]: /characters/Kzelchpakx\n";
+        let broken = broken_links("campaigns/test", source)
+            .into_iter()
+            .map(|link| (link.line, link.target))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            broken,
+            [
+                (
+                    5,
+                    BrokenTarget::Section {
+                        article: "characters/Dafna".to_owned(),
+                        section: "нет-такой".to_owned(),
+                    }
+                ),
+                (5, BrokenTarget::File("campaigns/map.png".to_owned())),
+                (7, BrokenTarget::Article("characters/Kzelchpakx".to_owned())),
+            ]
+        );
+    }
+
+    /// Fails the build when an article in `resources/articles` links nowhere.
+    #[test]
+    fn articles_have_no_broken_links() {
+        let broken = articles::articles()
+            .iter()
+            .flat_map(|article| broken_links(article.path, article.source))
+            .map(|link| link.to_string())
+            .collect::<Vec<_>>();
+        assert!(broken.is_empty(), "{}", broken.join("\n"));
+    }
+
+    #[test]
+    fn backlinks_carry_the_sentence_around_the_link() {
+        let dafna = backlinks("characters/Dafna");
+        assert_eq!(dafna.len(), 1);
+        assert_eq!(dafna[0].from.path, "campaigns/Tournament/campaign");
+        assert_eq!(dafna[0].sentences, ["Дафна - дворф, клерик Бахамута."]);
+
+        assert_eq!(
+            backlinks("campaigns/Tournament/session_1")[0].from.path,
+            "campaigns/Tournament/campaign"
+        );
+        assert!(backlinks("characters/Kzelchpakx").is_empty());
+    }
+}
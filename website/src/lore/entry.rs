@@ -0,0 +1,95 @@
+//! Articles of the tree and what their links are resolved by.
+
+use pulldown_cmark::HeadingLevel;
+
+use super::{front_matter, outline, ArticleMeta, ARTICLE_OPTIONS};
+
+/// Article of the tree, with what its links are resolved by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArticleEntry {
+    /// Path without the extension, such as `characters/Nitros`.
+    pub path: &'static str,
+    pub source: &'static str,
+
+    /// Title from the front matter, or else the first top-level heading, or else the file name.
+    pub title: String,
+    pub meta: ArticleMeta,
+}
+
+impl ArticleEntry {
+    pub fn new(path: &'static str, source: &'static str) -> Self {
+        let meta = front_matter(source, ARTICLE_OPTIONS)
+            .and_then(|(kind, text)| ArticleMeta::parse(kind, &text).ok())
+            .unwrap_or_default();
+        let title = meta
+            .title
+            .clone()
+            .or_else(|| {
+                outline(source, ARTICLE_OPTIONS)
+                    .into_iter()
+                    .find(|entry| entry.level == HeadingLevel::H1)
+                    .map(|entry| entry.text)
+            })
+            .unwrap_or_else(|| file_name(path).to_owned());
+
+        ArticleEntry {
+            path,
+            source,
+            title,
+            meta,
+        }
+    }
+
+    pub fn file_name(&self) -> &'static str {
+        file_name(self.path)
+    }
+}
+
+fn file_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+pub fn find_in<'a>(articles: &'a [ArticleEntry], target: &str) -> Option<&'a ArticleEntry> {
+    let target = target.trim();
+    let target = target
+        .strip_suffix(".md")
+        .unwrap_or(target)
+        .trim_matches('/')
+        .to_lowercase();
+    let named = |name: &str| name.to_lowercase() == target;
+
+    articles
+        .iter()
+        .find(|article| named(article.path))
+        .or_else(|| articles.iter().find(|article| named(article.file_name())))
+        .or_else(|| articles.iter().find(|article| named(&article.title)))
+        .or_else(|| {
+            articles
+                .iter()
+                .find(|article| article.meta.aliases.iter().any(|alias| named(alias)))
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn articles_are_found_by_path_file_name_title_and_alias() {
+        let articles = [
+            ArticleEntry::new("characters/Nitros", "# Нитрос\n\nЭльф.\n"),
+            ArticleEntry::new(
+                "characters/Dafna",
+                "---\naliases: [Nitros, Жрица]\n---\n# Дафна\n",
+            ),
+        ];
+        let find = |target| find_in(&articles, target).map(|article| article.path);
+
+        assert_eq!(articles[0].title, "Нитрос");
+        assert_eq!(find("characters/Nitros.md"), Some("characters/Nitros"));
+        assert_eq!(find("nitros"), Some("characters/Nitros"));
+        assert_eq!(find("НИТРОС"), Some("characters/Nitros"));
+        assert_eq!(find("жрица"), Some("characters/Dafna"));
+        assert_eq!(find("Кзельчпакх"), None);
+    }
+}
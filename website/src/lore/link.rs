@@ -0,0 +1,141 @@
+//! Resolution of the links written in articles.
+//!
+//! Articles link to each other the way they are laid out in `resources/articles`:
+//! `./session_1.md` is next to the current article, `../characters/Nitros.md` is in
+//! a sibling folder and `/characters/Nitros` starts from the articles root.
+
+use percent_encoding::percent_decode_str;
+
+/// Where a link of an article leads.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ArticleLink {
+    /// Another article, or a section of it, by its path without the `.md` extension.
+    Article { path: String, section: String },
+
+    /// Section of the current article.
+    Section(String),
+
+    /// Page outside the site, opened in a new tab.
+    External(String),
+}
+
+/// Whether the destination starts with a URL scheme, such as `https:` or `mailto:`.
+fn has_scheme(destination: &str) -> bool {
+    let Some((scheme, _)) = destination.split_once(':') else {
+        return false;
+    };
+    let mut chars = scheme.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn decode(text: &str) -> String {
+    percent_decode_str(text).decode_utf8_lossy().into_owned()
+}
+
+/// Resolves the destination of a link in the article at `article`, a path such as
+/// `campaign/session_1`, for the site served under `base_path`.
+pub fn resolve(base_path: Option<&str>, article: &str, destination: &str) -> ArticleLink {
+    let destination = destination.trim();
+    if has_scheme(destination) || destination.starts_with("//") {
+        return ArticleLink::External(destination.to_owned());
+    }
+
+    let (path, section) = destination.split_once('#').unwrap_or((destination, ""));
+    let section = decode(section);
+    if path.is_empty() {
+        return ArticleLink::Section(section);
+    }
+
+    let mut segments = Vec::new();
+    let relative = match path.strip_prefix('/') {
+        Some(absolute) => {
+            // Links copied from the address bar carry the base path and the route prefix.
+            let base_path = base_path.map(|base| base.trim_matches('/'));
+            let absolute = match base_path {
+                Some(base) if !base.is_empty() => absolute
+                    .strip_prefix(base)
+                    .and_then(|rest| rest.strip_prefix('/'))
+                    .unwrap_or(absolute),
+                _ => absolute,
+            };
+            absolute.strip_prefix("article/").unwrap_or(absolute)
+        }
+        None => {
+            segments.extend(article.split('/'));
+            segments.pop();
+            path
+        }
+    };
+    for segment in relative.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
+    }
+
+    let path = segments
+        .into_iter()
+        .map(decode)
+        .collect::<Vec<_>>()
+        .join("/");
+    let path = path.strip_suffix(".md").unwrap_or(&path).to_owned();
+    ArticleLink::Article { path, section }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn article(path: &str, section: &str) -> ArticleLink {
+        ArticleLink::Article {
+            path: path.to_owned(),
+            section: section.to_owned(),
+        }
+    }
+
+    #[test]
+    fn relative_links_follow_the_article() {
+        let resolve = |destination| resolve(None, "campaign/campaign", destination);
+        assert_eq!(resolve("./session_1.md"), article("campaign/session_1", ""));
+        assert_eq!(resolve("session_1"), article("campaign/session_1", ""));
+        assert_eq!(
+            resolve("../characters/Nitros.md#История"),
+            article("characters/Nitros", "История")
+        );
+        assert_eq!(
+            resolve("../characters/%D0%94%D0%B0%D1%84%D0%BD%D0%B0.md"),
+            article("characters/Дафна", "")
+        );
+        assert_eq!(resolve("#семья"), ArticleLink::Section("семья".to_owned()));
+    }
+
+    #[test]
+    fn absolute_links_start_from_the_root() {
+        let resolve = |destination| resolve(Some("my-dnd-world"), "campaign", destination);
+        assert_eq!(
+            resolve("/characters/Nitros"),
+            article("characters/Nitros", "")
+        );
+        assert_eq!(
+            resolve("/my-dnd-world/article/characters/Nitros#семья"),
+            article("characters/Nitros", "семья")
+        );
+        assert_eq!(resolve("/article/campaign.md"), article("campaign", ""));
+    }
+
+    #[test]
+    fn urls_with_a_scheme_are_external() {
+        let resolve = |destination| resolve(None, "campaign", destination);
+        for url in [
+            "https://www.dndbeyond.com/",
+            "mailto:dm@example.com",
+            "//example.com/map.png",
+        ] {
+            assert_eq!(resolve(url), ArticleLink::External(url.to_owned()));
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Front matter of the articles.
+
+use std::fmt;
+
+use pulldown_cmark::MetadataBlockKind;
+
+/// Who the article is listed for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Visibility {
+    #[default]
+    Public,
+
+    /// Reachable by its link, but left out of listings and search.
+    Unlisted,
+
+    /// Work in progress, shown with a notice.
+    Draft,
+}
+
+/// Front matter of an article, written either as YAML between `---` lines
+/// or as TOML between `+++` lines at the top of the article:
+///
+/// ```markdown
+/// ---
+/// title: Nitros
+/// summary: Tiefling sorcerer from the southern wastes
+/// tags: [character, sorcerer]
+/// aliases: [The Ember]
+/// related_characters: [characters/Dafna]
+/// character: "123456789"
+/// ---
+/// ```
+///
+/// Every field is optional and unknown fields are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Deserialize)]
+#[serde(default)]
+pub struct ArticleMeta {
+    pub title: Option<String>,
+    pub summary: Option<String>,
+    pub tags: Vec<String>,
+
+    /// Other names the article can be linked by.
+    pub aliases: Vec<String>,
+    pub visibility: Visibility,
+
+    /// Paths of the articles of related characters, such as `characters/Dafna`.
+    pub related_characters: Vec<String>,
+
+    /// Id of the stored character whose stat block is shown beside the article.
+    pub character: Option<String>,
+
+    /// Share code of a character embedded in the article, shown when no stored one is found.
+    pub character_code: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArticleMetaError {
+    Yaml(String),
+    Toml(String),
+}
+
+impl fmt::Display for ArticleMetaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArticleMetaError::Yaml(err) => write!(f, "Invalid YAML front matter: {err}"),
+            ArticleMetaError::Toml(err) => write!(f, "Invalid TOML front matter: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ArticleMetaError {}
+
+impl ArticleMeta {
+    pub fn parse(kind: MetadataBlockKind, text: &str) -> Result<Self, ArticleMetaError> {
+        if text.trim().is_empty() {
+            return Ok(ArticleMeta::default());
+        }
+        match kind {
+            MetadataBlockKind::YamlStyle => {
+                serde_yaml::from_str(text).map_err(|err| ArticleMetaError::Yaml(err.to_string()))
+            }
+            MetadataBlockKind::PlusesStyle => {
+                toml::from_str(text).map_err(|err| ArticleMetaError::Toml(err.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn yaml_and_toml_front_matter() {
+        let yaml = ArticleMeta::parse(
+            MetadataBlockKind::YamlStyle,
+            "title: Nitros\ntags: [character, sorcerer]\nvisibility: draft\ncharacter: \"42\"\n",
+        )
+        .unwrap();
+        let toml = ArticleMeta::parse(
+            MetadataBlockKind::PlusesStyle,
+            "title = \"Nitros\"\ntags = [\"character\", \"sorcerer\"]\nvisibility = \"draft\"\ncharacter = \"42\"\n",
+        )
+        .unwrap();
+
+        assert_eq!(yaml, toml);
+        assert_eq!(yaml.title.as_deref(), Some("Nitros"));
+        assert_eq!(yaml.tags, ["character", "sorcerer"]);
+        assert_eq!(yaml.visibility, Visibility::Draft);
+        assert_eq!(yaml.character.as_deref(), Some("42"));
+        assert!(yaml.aliases.is_empty());
+    }
+
+    #[test]
+    fn invalid_front_matter_is_reported() {
+        assert!(matches!(
+            ArticleMeta::parse(MetadataBlockKind::YamlStyle, "tags: [unclosed"),
+            Err(ArticleMetaError::Yaml(_))
+        ));
+        assert_eq!(
+            ArticleMeta::parse(MetadataBlockKind::PlusesStyle, "\n"),
+            Ok(ArticleMeta::default())
+        );
+    }
+}
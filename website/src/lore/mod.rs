@@ -0,0 +1,13 @@
+//! Reading of the articles in `resources/articles` that needs no renderer.
+//!
+//! The build script compiles these modules as well, so that the backlinks and the
+//! search index of the articles are built before the site is served. They depend on
+//! nothing but the Markdown parser, the stemmers and the data crates.
+
+mod entry;
+mod link;
+mod meta;
+mod outline;
+mod terms;
+
+pub use self::{entry::*, link::*, meta::*, outline::*, terms::*};
@@ -0,0 +1,146 @@
+//! Markdown options, headings and front matter of the articles.
+
+use std::collections::HashSet;
+
+use pulldown_cmark::{
+    CowStr, Event, HeadingLevel, MetadataBlockKind, Options, Parser, Tag, TagEnd,
+};
+
+/// Extensions enabled for the lore articles.
+pub const ARTICLE_OPTIONS: Options = Options::ENABLE_TABLES
+    .union(Options::ENABLE_FOOTNOTES)
+    .union(Options::ENABLE_STRIKETHROUGH)
+    .union(Options::ENABLE_TASKLISTS)
+    .union(Options::ENABLE_HEADING_ATTRIBUTES)
+    .union(Options::ENABLE_DEFINITION_LIST)
+    .union(Options::ENABLE_MATH)
+    .union(Options::ENABLE_GFM)
+    .union(Options::ENABLE_YAML_STYLE_METADATA_BLOCKS)
+    .union(Options::ENABLE_PLUSES_DELIMITED_METADATA_BLOCKS)
+    .union(Options::ENABLE_WIKILINKS);
+
+/// Heading of a text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OutlineEntry {
+    pub level: HeadingLevel,
+
+    /// Unique id of the heading element, the target of `#id` links.
+    pub id: String,
+    pub text: String,
+}
+
+/// Anchor id made of the words of a heading: `Ранние годы` becomes `ранние-годы`.
+///
+/// Letters of every script are kept, so that the ids of Russian headings stay readable.
+pub fn slug(text: &str) -> String {
+    let mut slug = String::new();
+    for c in text.chars().flat_map(char::to_lowercase) {
+        if c.is_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if (c.is_whitespace() || c == '-') && !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_end_matches('-');
+    if slug.is_empty() {
+        "section".to_owned()
+    } else {
+        slug.to_owned()
+    }
+}
+
+/// Headings of the text in order, each with a unique id.
+///
+/// Ids written by the author as `{#id}` are kept. Other headings get the slug of their
+/// text, followed by `-1`, `-2` and so on when an earlier heading took it.
+pub fn outline(content: &str, options: Options) -> Vec<OutlineEntry> {
+    let mut headings = Vec::new();
+    let mut current = None::<(HeadingLevel, Option<CowStr>, String)>;
+    for event in Parser::new_ext(content, options) {
+        match event {
+            Event::Start(Tag::Heading { level, id, .. }) => {
+                current = Some((level, id, String::new()));
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some((_, _, heading)) = &mut current {
+                    heading.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::Heading(_)) => headings.extend(current.take()),
+            _ => {}
+        }
+    }
+
+    let mut taken = headings
+        .iter()
+        .filter_map(|(_, id, _)| Some(id.as_ref()?.to_string()))
+        .collect::<HashSet<_>>();
+    headings
+        .into_iter()
+        .map(|(level, id, text)| {
+            let id = match id {
+                Some(id) => id.to_string(),
+                None => {
+                    let base = slug(&text);
+                    let mut id = base.clone();
+                    for number in 1.. {
+                        if !taken.contains(&id) {
+                            break;
+                        }
+                        id = format!("{base}-{number}");
+                    }
+                    taken.insert(id.clone());
+                    id
+                }
+            };
+            OutlineEntry {
+                level,
+                id,
+                text: text.trim().to_owned(),
+            }
+        })
+        .collect()
+}
+
+/// Kind and text of the front matter block at the top of the text.
+pub fn front_matter(content: &str, options: Options) -> Option<(MetadataBlockKind, String)> {
+    let mut parser = Parser::new_ext(content, options);
+    let Some(Event::Start(Tag::MetadataBlock(kind))) = parser.next() else {
+        return None;
+    };
+    let mut text = String::new();
+    for event in parser {
+        match event {
+            Event::Text(piece) => text.push_str(&piece),
+            _ => break,
+        }
+    }
+    Some((kind, text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_keep_cyrillic_words() {
+        assert_eq!(slug("Ранние годы"), "ранние-годы");
+        assert_eq!(slug("  Nitros: the *Ember*!  "), "nitros-the-ember");
+        assert_eq!(slug("Ёж - 2"), "ёж-2");
+        assert_eq!(slug("?!"), "section");
+    }
+
+    #[test]
+    fn outline_ids_are_unique() {
+        let content =
+            "# Нитрос\n\n## История\n\n## История\n\n### Семья {#история-1}\n\n## `Code` тоже\n";
+        let ids = outline(content, ARTICLE_OPTIONS)
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ids,
+            ["нитрос", "история", "история-2", "история-1", "code-тоже"]
+        );
+    }
+}
@@ -0,0 +1,39 @@
+//! Words of the articles and the stems they are searched by.
+
+use std::{ops::Range, sync::LazyLock};
+
+use rust_stemmers::{Algorithm, Stemmer};
+
+static RUSSIAN: LazyLock<Stemmer> = LazyLock::new(|| Stemmer::create(Algorithm::Russian));
+static ENGLISH: LazyLock<Stemmer> = LazyLock::new(|| Stemmer::create(Algorithm::English));
+
+/// Words of the text with their byte ranges, a word being a run of letters and digits
+/// of any script.
+pub fn words(text: &str) -> impl Iterator<Item = (Range<usize>, &str)> {
+    let mut rest = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        let (start, _) = rest.find(|(_, c)| c.is_alphanumeric())?;
+        let mut end = text.len();
+        while let Some(&(i, c)) = rest.peek() {
+            if !c.is_alphanumeric() {
+                end = i;
+                break;
+            }
+            rest.next();
+        }
+        Some((start..end, &text[start..end]))
+    })
+}
+
+/// Stem of the word the index and the queries are matched by: lowercase, with `ё`
+/// written as `е` and the ending cut off by the stemmer of its script.
+pub fn term(word: &str) -> String {
+    let word = word.to_lowercase().replace('ё', "е");
+    if word.chars().any(|c| matches!(c, 'а'..='я')) {
+        RUSSIAN.stem(&word).into_owned()
+    } else if word.chars().all(|c| c.is_ascii_alphabetic()) {
+        ENGLISH.stem(&word).into_owned()
+    } else {
+        word
+    }
+}
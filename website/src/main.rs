@@ -2,14 +2,20 @@ use std::fmt::Display;
 
 use dioxus::{logger::tracing::info, prelude::*, router::prelude::*};
 
+mod article;
+mod articles;
 mod character;
+mod link;
+mod lore;
 mod markdown;
 mod nav;
 mod props;
+mod search;
+mod widget;
 
 use self::{
-    character::{Character, CharactersList},
-    markdown::Markdown,
+    character::{Character, CharactersList, Compare},
+    markdown::{Markdown, MissingArticle},
     nav::Navigation,
 };
 
@@ -27,17 +33,26 @@ enum Route {
         #[route("/")]
         Home { },
 
-        #[route("/characters")]
-        CharactersList,
+        #[route("/characters#:share")]
+        CharactersList {
+            share: String,
+        },
+
+        #[route("/compare?:left&:right")]
+        Compare {
+            left: String,
+            right: String,
+        },
 
         #[route("/character/:id")]
         Character {
             id: String,
         },
 
-        #[route("/article/:..segments")]
+        #[route("/article/:..segments#:section")]
         Article {
             segments: Vec<String>,
+            section: String,
         },
     #[end_layout]
 
@@ -48,19 +63,22 @@ enum Route {
 }
 
 #[component]
-pub fn Article(segments: Vec<String>) -> Element {
+pub fn Article(segments: Vec<String>, section: String) -> Element {
     let path = join_strings(segments.iter(), '/').unwrap_or_else(|| "index".to_string());
 
     tracing::debug!("Article path: {}", path);
 
-    let url = if path.ends_with(".md") {
-        format!("https://raw.githubusercontent.com/zakarumych/my-dnd-world/refs/heads/main/resources/articles/{path}")
-    } else {
-        format!("https://raw.githubusercontent.com/zakarumych/my-dnd-world/refs/heads/main/resources/articles/{path}.md")
-    };
+    let path = path.strip_suffix(".md").unwrap_or(&path).to_owned();
 
-    rsx! {
-        Markdown { url }
+    // Articles are embedded by the build script, the same copy the outline, backlinks,
+    // search index and link checks are built from.
+    match articles::article(&path) {
+        Some(article) => rsx! {
+            Markdown { content: article.source, path, section }
+        },
+        None => rsx! {
+            MissingArticle { path }
+        },
     }
 }
 
@@ -1,15 +1,23 @@
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fmt::{self, Display},
 };
 
 use dioxus::prelude::*;
 use pulldown_cmark::{
-    Alignment, BlockQuoteKind, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType, Parser, Tag,
-    TagEnd,
+    Alignment, BlockQuoteKind, CodeBlockKind, CowStr, Event, HeadingLevel, LinkType,
+    MetadataBlockKind, Options, Parser, Tag, TagEnd,
 };
 
-use crate::{join_strings, props::ArmorCategory};
+use crate::{
+    article::{scroll_to_anchor, ArticleAside, ArticleHeader, Backlinks, TableOfContents},
+    articles::find_article,
+    join_strings,
+    link::{article_route, broken_links},
+    lore::{outline, slug, ArticleLink, ArticleMeta, OutlineEntry, ARTICLE_OPTIONS},
+    props::ArmorCategory,
+    widget::{widget, CodeBlockWidget},
+};
 
 struct HtmlEscaped<S>(S);
 
@@ -92,22 +100,174 @@ enum TableState {
 }
 
 struct Context<'a> {
-    numbers: HashMap<CowStr<'a>, usize>,
+    /// Path of the article, that relative links start from.
+    article: String,
+
+    footnote_index: FootnoteIndex,
+
+    /// References to each footnote rendered so far.
+    footnote_references: HashMap<CowStr<'a>, usize>,
+
+    /// Rendered definitions and counts of references by footnote number,
+    /// listed at the end of the text.
+    footnotes: BTreeMap<usize, (Element, usize)>,
 
     table_state: TableState,
     table_alignments: Vec<Alignment>,
     table_cell_index: usize,
     in_non_writing_block: bool,
+
+    /// Kind and text of the front matter block.
+    metadata: Option<(MetadataBlockKind, String)>,
+
+    outline: Vec<OutlineEntry>,
+    heading_index: usize,
 }
 
 impl<'a> Context<'a> {
-    pub fn new() -> Self {
+    pub fn new(article: String, outline: Vec<OutlineEntry>, footnote_index: FootnoteIndex) -> Self {
         Context {
+            article,
+            outline,
+            heading_index: 0,
+            footnote_index,
+            footnote_references: HashMap::new(),
+            footnotes: BTreeMap::new(),
             table_state: TableState::Head,
             table_alignments: vec![],
             table_cell_index: 0,
-            numbers: HashMap::new(),
             in_non_writing_block: false,
+            metadata: None,
+        }
+    }
+}
+
+/// Footnotes of a text, numbered in the order of their first references.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FootnoteIndex {
+    /// Number of every defined footnote.
+    ///
+    /// Footnotes that are never referenced are numbered after the others.
+    pub numbers: HashMap<String, usize>,
+
+    /// Number of references to each footnote.
+    pub references: HashMap<String, usize>,
+
+    /// Undefined and redefined footnotes.
+    pub warnings: Vec<String>,
+}
+
+/// Names of the `[^name]` references left as text in the text of a paragraph.
+///
+/// The parser only reads a reference as one when the footnote is defined.
+fn undefined_footnotes(text: &str) -> impl Iterator<Item = &str> {
+    text.split("[^").skip(1).filter_map(|rest| {
+        let (name, _) = rest.split_once(']')?;
+        (!name.is_empty() && !name.contains(char::is_whitespace)).then_some(name)
+    })
+}
+
+pub fn footnote_index(content: &str, options: Options) -> FootnoteIndex {
+    let mut index = FootnoteIndex::default();
+    let mut definitions = Vec::new();
+    let mut undefined = Vec::new();
+
+    // Text of a paragraph comes in pieces, split at the brackets.
+    let mut text = String::new();
+    let mut in_code_block = false;
+    for event in Parser::new_ext(content, options) {
+        if let Event::Text(piece) = &event {
+            if !in_code_block {
+                text.push_str(piece);
+            }
+            continue;
+        }
+        for name in undefined_footnotes(&text) {
+            if !undefined.iter().any(|undefined| undefined == name) {
+                undefined.push(name.to_owned());
+            }
+        }
+        text.clear();
+
+        match event {
+            Event::FootnoteReference(name) => {
+                let next = index.numbers.len() + 1;
+                index.numbers.entry(name.to_string()).or_insert(next);
+                *index.references.entry(name.to_string()).or_insert(0) += 1;
+            }
+            Event::Start(Tag::FootnoteDefinition(name)) => {
+                if definitions.contains(&name) {
+                    index
+                        .warnings
+                        .push(format!("Footnote [^{name}] is defined more than once"));
+                }
+                definitions.push(name);
+            }
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            _ => {}
+        }
+    }
+    for name in undefined_footnotes(&text) {
+        if !undefined.iter().any(|undefined| undefined == name) {
+            undefined.push(name.to_owned());
+        }
+    }
+
+    for name in definitions {
+        let next = index.numbers.len() + 1;
+        index.numbers.entry(name.to_string()).or_insert(next);
+    }
+    index.warnings.extend(
+        undefined
+            .into_iter()
+            .map(|name| format!("Footnote [^{name}] is referenced but never defined")),
+    );
+    index
+}
+
+fn footnote_id(number: usize) -> String {
+    format!("fn-{number}")
+}
+
+/// Id of a reference to a footnote, counting the references to it from 1.
+fn footnote_reference_id(number: usize, occurrence: usize) -> String {
+    format!("fnref-{number}-{occurrence}")
+}
+
+/// Numbered footnotes, each with links back to all of its references.
+#[component]
+fn FootnotesSection(footnotes: Vec<(usize, Element, usize)>) -> Element {
+    if footnotes.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section {
+            class: "footnotes",
+            hr {}
+            ol {
+                for (number, definition, references) in footnotes {
+                    li {
+                        key: "{number}",
+                        id: footnote_id(number),
+                        value: number as i64,
+                        {definition}
+                        for occurrence in 1..=references {
+                            a {
+                                class: "footnote-backref",
+                                href: "#{footnote_reference_id(number, occurrence)}",
+                                title: "Back to reference {occurrence}",
+                                onclick: move |e| {
+                                    e.prevent_default();
+                                    scroll_to_anchor(&footnote_reference_id(number, occurrence));
+                                },
+                                if occurrence == 1 { "↩" } else { "↩{occurrence}" }
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
 }
@@ -125,11 +285,16 @@ fn pull_elements<'a, 'b: 'a>(
                 }
                 Tag::Heading {
                     level,
-                    id,
+                    id: _,
                     classes,
                     attrs,
                 } => {
-                    let id = id.map(|id| escape_html(id).to_string());
+                    // Same order as the outline, which holds the unique ids.
+                    let id = cx
+                        .outline
+                        .get(cx.heading_index)
+                        .map(|entry| entry.id.clone());
+                    cx.heading_index += 1;
                     let classes = join_strings(classes.iter().map(|class| escape_html(class)), ' ');
 
                     match level {
@@ -184,7 +349,17 @@ fn pull_elements<'a, 'b: 'a>(
                     }
                     CodeBlockKind::Fenced(info) => {
                         let lang = info.split(' ').next().unwrap();
-                        if lang.is_empty() {
+                        if widget(lang).is_some() {
+                            let mut body = String::new();
+                            for event in iter.by_ref() {
+                                match event {
+                                    Event::Text(text) => body.push_str(&text),
+                                    Event::End(TagEnd::CodeBlock) => break,
+                                    _ => {}
+                                }
+                            }
+                            rsx! { CodeBlockWidget { language: lang, body } }
+                        } else if lang.is_empty() {
                             rsx! { pre { code { {pull_elements(iter, cx)} } } }
                         } else {
                             let class_str = format!(" class=\"language-{}\"", escape_html(lang));
@@ -208,20 +383,15 @@ fn pull_elements<'a, 'b: 'a>(
                     rsx! { li { {pull_elements(iter, cx)} } }
                 }
                 Tag::FootnoteDefinition(name) => {
-                    let id = escape_html(&name).to_string();
-                    let len = cx.numbers.len() + 1;
-                    let number = *cx.numbers.entry(name).or_insert(len);
-                    rsx! {
-                        div {
-                            id: id,
-                            class: "footnote-definition",
-                            sup {
-                                class: "footnote-definition-label",
-                                {number.to_string()}
-                            }
-                            {pull_elements(iter, cx)}
-                        }
+                    // Rendered in place to consume its events, but shown in the footnotes section.
+                    let definition = rsx! { {pull_elements(iter, cx)} };
+                    if let Some(&number) = cx.footnote_index.numbers.get(&*name) {
+                        let references = cx.footnote_index.references.get(&*name).copied();
+                        cx.footnotes
+                            .entry(number)
+                            .or_insert((definition, references.unwrap_or(0)));
                     }
+                    rsx! {}
                 }
                 Tag::DefinitionList => {
                     rsx! { dl { {pull_elements(iter, cx)} } }
@@ -275,30 +445,81 @@ fn pull_elements<'a, 'b: 'a>(
                     let link = format!("mailto:{}", escape_href(dest_url));
 
                     rsx! {
-                        Link {
-                            to: link,
-                            title: if title.is_empty() { &*title },
+                        a {
+                            href: link,
+                            title: if !title.is_empty() { &*title },
                             {pull_elements(iter, cx)}
                         }
                     }
                 }
 
                 Tag::Link {
-                    link_type: _,
+                    link_type: LinkType::WikiLink { .. },
                     dest_url,
-                    title,
+                    title: _,
                     id: _,
                 } => {
-                    let link = escape_href(dest_url).to_string();
+                    let (target, section) = dest_url.split_once('#').unwrap_or((&dest_url, ""));
+                    let section = if section.is_empty() {
+                        String::new()
+                    } else {
+                        slug(section)
+                    };
+                    match find_article(target) {
+                        Some(article) => rsx! {
+                            Link {
+                                class: "wiki-link",
+                                to: article_route(article.path, &section),
+                                title: "{article.title}",
+                                {pull_elements(iter, cx)}
+                            }
+                        },
+                        // Leads to the stub of the article that is yet to be written.
+                        None => rsx! {
+                            Link {
+                                class: "wiki-missing",
+                                to: article_route(target.trim(), ""),
+                                title: "No article named {target} yet",
+                                {pull_elements(iter, cx)}
+                            }
+                        },
+                    }
+                }
 
-                    rsx! {
+                Tag::Link {
+                    link_type: _,
+                    dest_url,
+                    title,
+                    id: _,
+                } => match ArticleLink::resolve(&cx.article, &dest_url) {
+                    ArticleLink::Article { path, section } => rsx! {
                         Link {
-                            to: link,
-                            title: if title.is_empty() { &*title },
+                            to: article_route(&path, &section),
+                            title: if !title.is_empty() { &*title },
                             {pull_elements(iter, cx)}
                         }
-                    }
-                }
+                    },
+                    ArticleLink::Section(section) => rsx! {
+                        a {
+                            href: "#{escape_href(&section)}",
+                            title: if !title.is_empty() { &*title },
+                            onclick: move |e| {
+                                e.prevent_default();
+                                scroll_to_anchor(&section);
+                            },
+                            {pull_elements(iter, cx)}
+                        }
+                    },
+                    ArticleLink::External(url) => rsx! {
+                        a {
+                            href: escape_href(url).to_string(),
+                            title: if !title.is_empty() { &*title },
+                            target: "_blank",
+                            rel: "noopener noreferrer",
+                            {pull_elements(iter, cx)}
+                        }
+                    },
+                },
 
                 Tag::Subscript => rsx! { sub { {pull_elements(iter, cx)} } },
                 Tag::Superscript => rsx! { sup { {pull_elements(iter, cx)} } },
@@ -316,8 +537,9 @@ fn pull_elements<'a, 'b: 'a>(
                         }
                     }
                 }
-                Tag::MetadataBlock(_) => {
+                Tag::MetadataBlock(kind) => {
                     cx.in_non_writing_block = true;
+                    cx.metadata = Some((kind, String::new()));
                     rsx! { {pull_elements(iter, cx)} }
                 }
             },
@@ -340,6 +562,9 @@ fn pull_elements<'a, 'b: 'a>(
                 if !cx.in_non_writing_block {
                     rsx! { {escape_html_body(text).to_string()} }
                 } else {
+                    if let Some((_, metadata)) = &mut cx.metadata {
+                        metadata.push_str(&text);
+                    }
                     rsx!()
                 }
             }
@@ -359,13 +584,23 @@ fn pull_elements<'a, 'b: 'a>(
                 rsx! { {html} }
             }
             Event::FootnoteReference(name) => {
-                let len = cx.numbers.len() + 1;
+                let number = cx.footnote_index.numbers.get(&*name).copied().unwrap_or(0);
+
+                let occurrence = cx.footnote_references.entry(name).or_insert(0);
+                *occurrence += 1;
+                let id = footnote_reference_id(number, *occurrence);
+                let target = footnote_id(number);
                 rsx! {
                     sup {
                         class: "footnote-reference",
-                        Link {
-                            to: format!("#{}", escape_href(name)),
-                            {len.to_string()}
+                        a {
+                            id,
+                            href: "#{target}",
+                            onclick: move |e| {
+                                e.prevent_default();
+                                scroll_to_anchor(&target);
+                            },
+                            "{number}"
                         }
                     }
                 }
@@ -390,28 +625,115 @@ fn pull_elements<'a, 'b: 'a>(
 }
 
 /// Render some text as markdown.
+///
+/// Front matter of the text is read as [`ArticleMeta`] and laid out around it.
 #[component]
-pub fn Markdown(url: String) -> Element {
-    let resource = use_resource(use_reactive!(|url| {
-        let url = url.clone();
-        tracing::debug!("Loading markdown ({url})");
-
-        async move { reqwest::get(&url).await.unwrap().text().await.unwrap() }
-    }));
+pub fn Markdown(
+    content: &'static str,
+    #[props(default = ARTICLE_OPTIONS)] options: Options,
+    /// Path of the article the text comes from, that its relative links start from.
+    #[props(default)]
+    path: String,
+    /// Id of the section to scroll to.
+    #[props(default)]
+    section: String,
+) -> Element {
+    let outline = outline(content, options);
+    let footnote_index = footnote_index(content, options);
+    let mut warnings = footnote_index.warnings.clone();
+    let mut parser = Parser::new_ext(content, options);
 
-    let reader = resource.read();
-    if let Some(content) = &*reader {
-        let mut parser = Parser::new(content);
+    let mut cx = Context::new(path.clone(), outline.clone(), footnote_index);
+    let elements = pull_elements(&mut parser, &mut cx).collect::<Vec<_>>();
+    let meta = match &cx.metadata {
+        None => ArticleMeta::default(),
+        Some((kind, text)) => ArticleMeta::parse(*kind, text).unwrap_or_else(|err| {
+            warnings.insert(0, err.to_string());
+            ArticleMeta::default()
+        }),
+    };
+    warnings.extend(
+        broken_links(&path, content)
+            .into_iter()
+            .map(|link| link.to_string()),
+    );
+    for warning in &warnings {
+        tracing::warn!("{path}: {warning}");
+    }
 
-        let mut cx = Context::new();
-        let elements = pull_elements(&mut parser, &mut cx);
+    let footnotes = std::mem::take(&mut cx.footnotes)
+        .into_iter()
+        .map(|(number, (definition, references))| (number, definition, references))
+        .collect::<Vec<_>>();
 
-        rsx! {
-            div { class: "md-content", {elements} }
+    rsx! {
+        div {
+            class: "flex flex-col gap-4 w-full",
+            // Diagnostics for the authors, left out of release builds.
+            if cfg!(debug_assertions) {
+                for warning in warnings {
+                    div { class: "text-yellow-700", "{warning}" }
+                }
+            }
+            ArticleHeader { meta: meta.clone() }
+            div {
+                class: "flex flex-wrap-reverse gap-8 items-start",
+                TableOfContents { outline, section }
+                div {
+                    class: "md-content flex-1",
+                    {elements.into_iter()}
+                    FootnotesSection { footnotes }
+                    Backlinks { path }
+                }
+                ArticleAside { meta }
+            }
         }
-    } else {
-        rsx! {
-            div { class: "md-content", "Loading..." }
+    }
+}
+
+/// Stub of an article that is linked to but not written yet.
+#[component]
+pub fn MissingArticle(path: String) -> Element {
+    let title = path.rsplit('/').next().unwrap_or(&path).to_owned();
+
+    rsx! {
+        div {
+            class: "md-content",
+            h1 { "{title}" }
+            p { "This article is not written yet." }
+            p {
+                "Write it in "
+                code { "resources/articles/{path}.md" }
+                " to fill this page."
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn footnotes_are_numbered_by_first_reference() {
+        let content = "[^b]: Defined first.\n\n\
+                       Text[^a] and[^b] again[^a] and[^missing].\n\n\
+                       [^a]: First.\n\n\
+                       [^unused]: Never referenced.\n\n\
+                       [^a]: Again.\n";
+        let index = footnote_index(content, ARTICLE_OPTIONS);
+
+        assert_eq!(index.numbers["a"], 1);
+        assert_eq!(index.numbers["b"], 2);
+        assert_eq!(index.numbers["unused"], 3);
+        assert!(!index.numbers.contains_key("missing"));
+        assert_eq!(index.references["a"], 2);
+        assert_eq!(
+            index.warnings,
+            [
+                "Footnote [^a] is defined more than once",
+                "Footnote [^missing] is referenced but never defined",
+            ]
+        );
+    }
+}
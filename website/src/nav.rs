@@ -1,6 +1,6 @@
 use dioxus::prelude::*;
 
-use crate::Route;
+use crate::{search::SearchBox, Route};
 
 #[component]
 pub fn Navigation() -> Element {
@@ -18,9 +18,10 @@ pub fn Navigation() -> Element {
                 }
                 Link {
                     class: "flex justify-center px-8 py-4 bg-gray-100 hover:bg-amber-200",
-                    to: Route::CharactersList { },
+                    to: Route::CharactersList { share: String::new() },
                     "Characters"
                 }
+                SearchBox {}
             }
 
             div {
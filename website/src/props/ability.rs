@@ -0,0 +1,60 @@
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Ability {
+    Strength,
+    Dexterity,
+    Constitution,
+    Intelligence,
+    Wisdom,
+    Charisma,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidAbility;
+
+impl FromStr for Ability {
+    type Err = InvalidAbility;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Strength" => Ok(Ability::Strength),
+            "Dexterity" => Ok(Ability::Dexterity),
+            "Constitution" => Ok(Ability::Constitution),
+            "Intelligence" => Ok(Ability::Intelligence),
+            "Wisdom" => Ok(Ability::Wisdom),
+            "Charisma" => Ok(Ability::Charisma),
+            _ => Err(InvalidAbility),
+        }
+    }
+}
+
+impl Ability {
+    pub const ALL: [Ability; 6] = [
+        Ability::Strength,
+        Ability::Dexterity,
+        Ability::Constitution,
+        Ability::Intelligence,
+        Ability::Wisdom,
+        Ability::Charisma,
+    ];
+
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Ability::Strength => "STR",
+            Ability::Dexterity => "DEX",
+            Ability::Constitution => "CON",
+            Ability::Intelligence => "INT",
+            Ability::Wisdom => "WIS",
+            Ability::Charisma => "CHA",
+        }
+    }
+}
+
+/// Highest score an ability can reach without magic or epic boons.
+pub const MAX_ABILITY_SCORE: u32 = 20;
+
+/// Ability modifier for the given score, rounded down.
+pub fn ability_modifier(score: u32) -> i32 {
+    (score as i32 - 10).div_euclid(2)
+}
@@ -0,0 +1,461 @@
+use std::fmt;
+
+use super::{Ability, Character, ChosenFeat, FeatError, Proficiency, Skill, MAX_ABILITY_SCORE};
+
+/// Tool proficiency granted by a background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BackgroundTool {
+    Fixed(&'static str),
+
+    /// Player picks one tool of the kind, such as "Musical Instrument".
+    Choice(&'static str),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Background {
+    pub name: &'static str,
+
+    /// Abilities that may be increased by the background.
+    pub abilities: [Ability; 3],
+    pub feat: &'static str,
+    pub skills: [Skill; 2],
+    pub tool: BackgroundTool,
+
+    /// Starting equipment package, taken instead of the starting gold.
+    pub equipment: &'static [&'static str],
+
+    /// Gold pieces left over in the equipment package.
+    pub equipment_gold: u32,
+}
+
+/// Gold pieces taken instead of the equipment package.
+pub const BACKGROUND_GOLD: u32 = 50;
+
+impl Background {
+    pub fn find(name: &str) -> Option<&'static Background> {
+        BACKGROUNDS
+            .iter()
+            .find(|background| background.name == name)
+    }
+
+    pub fn all() -> &'static [Background] {
+        BACKGROUNDS
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum StartingEquipment {
+    Package,
+    Gold,
+}
+
+/// Background chosen by the character together with its sub-choices.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ChosenBackground {
+    pub name: String,
+
+    /// Three ability picks: three different abilities for +1 each,
+    /// or one ability listed twice for +2 and another one for +1.
+    pub abilities: Vec<Ability>,
+
+    /// Origin feat granted by the background.
+    pub feat: ChosenFeat,
+
+    /// Tool picked when the background lets the player choose one.
+    #[serde(default)]
+    pub tool: Option<String>,
+
+    pub equipment: StartingEquipment,
+}
+
+impl ChosenBackground {
+    pub fn background(&self) -> Option<&'static Background> {
+        Background::find(&self.name)
+    }
+
+    /// Tool proficiency granted by the background.
+    pub fn tool(&self) -> Option<&str> {
+        match self.background()?.tool {
+            BackgroundTool::Fixed(tool) => Some(tool),
+            BackgroundTool::Choice(_) => self.tool.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BackgroundError {
+    UnknownBackground(String),
+    AbilityPicks,
+    AbilityNotAllowed(Ability),
+    AbilityScoreAboveMaximum(Ability),
+    WrongFeat { expected: &'static str },
+    Feat(FeatError),
+    MissingTool(&'static str),
+}
+
+impl fmt::Display for BackgroundError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BackgroundError::UnknownBackground(name) => write!(f, "Unknown background: {name}"),
+            BackgroundError::AbilityPicks => {
+                write!(
+                    f,
+                    "Increase three abilities by 1, or one by 2 and another by 1"
+                )
+            }
+            BackgroundError::AbilityNotAllowed(ability) => {
+                write!(f, "{ability:?} cannot be increased by this background")
+            }
+            BackgroundError::AbilityScoreAboveMaximum(ability) => {
+                write!(
+                    f,
+                    "{ability:?} cannot be increased above {MAX_ABILITY_SCORE}"
+                )
+            }
+            BackgroundError::WrongFeat { expected } => {
+                write!(f, "The background grants the {expected} feat")
+            }
+            BackgroundError::Feat(err) => err.fmt(f),
+            BackgroundError::MissingTool(kind) => write!(f, "Choose a {kind}"),
+        }
+    }
+}
+
+impl std::error::Error for BackgroundError {}
+
+impl From<FeatError> for BackgroundError {
+    fn from(err: FeatError) -> Self {
+        BackgroundError::Feat(err)
+    }
+}
+
+impl Character {
+    pub fn background(&self) -> Option<&ChosenBackground> {
+        self.background.as_ref()
+    }
+
+    /// Replaces the background of the character.
+    ///
+    /// Nothing is changed if any check fails.
+    pub fn set_background(&mut self, chosen: ChosenBackground) -> Result<(), BackgroundError> {
+        let background = chosen
+            .background()
+            .ok_or_else(|| BackgroundError::UnknownBackground(chosen.name.clone()))?;
+
+        // Checks are made against the character without its current background.
+        let mut next = self.clone();
+        next.background = None;
+
+        if chosen.abilities.len() != 3 {
+            return Err(BackgroundError::AbilityPicks);
+        }
+        for &ability in &chosen.abilities {
+            let increase = chosen.abilities.iter().filter(|&&a| a == ability).count() as u32;
+            if !background.abilities.contains(&ability) {
+                return Err(BackgroundError::AbilityNotAllowed(ability));
+            }
+            if increase > 2 {
+                return Err(BackgroundError::AbilityPicks);
+            }
+            if next.ability_score(ability) + increase > MAX_ABILITY_SCORE {
+                return Err(BackgroundError::AbilityScoreAboveMaximum(ability));
+            }
+        }
+
+        if chosen.feat.name != background.feat {
+            return Err(BackgroundError::WrongFeat {
+                expected: background.feat,
+            });
+        }
+        next.check_feat(&chosen.feat, next.total_level())?;
+
+        if let BackgroundTool::Choice(kind) = background.tool {
+            if chosen
+                .tool
+                .as_deref()
+                .is_none_or(|tool| tool.trim().is_empty())
+            {
+                return Err(BackgroundError::MissingTool(kind));
+            }
+        }
+
+        next.background = Some(chosen);
+        *self = next;
+        Ok(())
+    }
+
+    /// Removes the background, leaving only the free-text origin.
+    pub fn clear_background(&mut self) -> Option<ChosenBackground> {
+        self.background.take()
+    }
+
+    /// Proficiencies granted by the background.
+    pub fn background_proficiencies(&self) -> Vec<Proficiency> {
+        let Some(chosen) = &self.background else {
+            return Vec::new();
+        };
+        let Some(background) = chosen.background() else {
+            return Vec::new();
+        };
+
+        let mut proficiencies = background
+            .skills
+            .iter()
+            .map(|&skill| Proficiency::Skill(skill))
+            .collect::<Vec<_>>();
+        if let Some(tool) = chosen.tool() {
+            proficiencies.push(Proficiency::Tool(tool.to_owned()));
+        }
+        proficiencies
+    }
+
+    /// Starting equipment and gold pieces granted by the background.
+    pub fn starting_equipment(&self) -> (&'static [&'static str], u32) {
+        let Some(chosen) = &self.background else {
+            return (&[], 0);
+        };
+        match (chosen.background(), chosen.equipment) {
+            (Some(background), StartingEquipment::Package) => {
+                (background.equipment, background.equipment_gold)
+            }
+            (_, StartingEquipment::Gold) => (&[], BACKGROUND_GOLD),
+            (None, _) => (&[], 0),
+        }
+    }
+}
+
+static BACKGROUNDS: &[Background] = &[
+    Background {
+        name: "Acolyte",
+        abilities: [Ability::Intelligence, Ability::Wisdom, Ability::Charisma],
+        feat: "Magic Initiate",
+        skills: [Skill::Insight, Skill::Religion],
+        tool: BackgroundTool::Fixed("Calligrapher's Supplies"),
+        equipment: &[
+            "Calligrapher's Supplies",
+            "Book (prayers)",
+            "Holy Symbol",
+            "Parchment (10 sheets)",
+            "Robe",
+        ],
+        equipment_gold: 8,
+    },
+    Background {
+        name: "Artisan",
+        abilities: [Ability::Strength, Ability::Dexterity, Ability::Intelligence],
+        feat: "Crafter",
+        skills: [Skill::Investigation, Skill::Persuasion],
+        tool: BackgroundTool::Choice("Artisan's Tools"),
+        equipment: &["Artisan's Tools", "2 Pouches", "Traveler's Clothes"],
+        equipment_gold: 32,
+    },
+    Background {
+        name: "Charlatan",
+        abilities: [Ability::Dexterity, Ability::Constitution, Ability::Charisma],
+        feat: "Skilled",
+        skills: [Skill::Deception, Skill::SleightOfHand],
+        tool: BackgroundTool::Fixed("Forgery Kit"),
+        equipment: &["Forgery Kit", "Costume", "Fine Clothes"],
+        equipment_gold: 15,
+    },
+    Background {
+        name: "Criminal",
+        abilities: [
+            Ability::Dexterity,
+            Ability::Constitution,
+            Ability::Intelligence,
+        ],
+        feat: "Alert",
+        skills: [Skill::SleightOfHand, Skill::Stealth],
+        tool: BackgroundTool::Fixed("Thieves' Tools"),
+        equipment: &[
+            "2 Daggers",
+            "Thieves' Tools",
+            "Crowbar",
+            "2 Pouches",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 16,
+    },
+    Background {
+        name: "Entertainer",
+        abilities: [Ability::Strength, Ability::Dexterity, Ability::Charisma],
+        feat: "Musician",
+        skills: [Skill::Acrobatics, Skill::Performance],
+        tool: BackgroundTool::Choice("Musical Instrument"),
+        equipment: &[
+            "Musical Instrument",
+            "2 Costumes",
+            "Mirror",
+            "Perfume",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 11,
+    },
+    Background {
+        name: "Farmer",
+        abilities: [Ability::Strength, Ability::Constitution, Ability::Wisdom],
+        feat: "Tough",
+        skills: [Skill::AnimalHandling, Skill::Nature],
+        tool: BackgroundTool::Fixed("Carpenter's Tools"),
+        equipment: &[
+            "Sickle",
+            "Carpenter's Tools",
+            "Healer's Kit",
+            "Iron Pot",
+            "Shovel",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 30,
+    },
+    Background {
+        name: "Guard",
+        abilities: [Ability::Strength, Ability::Intelligence, Ability::Wisdom],
+        feat: "Alert",
+        skills: [Skill::Athletics, Skill::Perception],
+        tool: BackgroundTool::Choice("Gaming Set"),
+        equipment: &[
+            "Spear",
+            "Light Crossbow",
+            "20 Bolts",
+            "Gaming Set",
+            "Hooded Lantern",
+            "Manacles",
+            "Quiver",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 12,
+    },
+    Background {
+        name: "Guide",
+        abilities: [Ability::Dexterity, Ability::Constitution, Ability::Wisdom],
+        feat: "Magic Initiate",
+        skills: [Skill::Stealth, Skill::Survival],
+        tool: BackgroundTool::Fixed("Cartographer's Tools"),
+        equipment: &[
+            "Shortbow",
+            "20 Arrows",
+            "Cartographer's Tools",
+            "Bedroll",
+            "Quiver",
+            "Tent",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 3,
+    },
+    Background {
+        name: "Hermit",
+        abilities: [Ability::Constitution, Ability::Wisdom, Ability::Charisma],
+        feat: "Healer",
+        skills: [Skill::Medicine, Skill::Religion],
+        tool: BackgroundTool::Fixed("Herbalism Kit"),
+        equipment: &[
+            "Quarterstaff",
+            "Herbalism Kit",
+            "Bedroll",
+            "Book (philosophy)",
+            "Lamp",
+            "Oil (3 flasks)",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 16,
+    },
+    Background {
+        name: "Merchant",
+        abilities: [
+            Ability::Constitution,
+            Ability::Intelligence,
+            Ability::Charisma,
+        ],
+        feat: "Lucky",
+        skills: [Skill::AnimalHandling, Skill::Persuasion],
+        tool: BackgroundTool::Fixed("Navigator's Tools"),
+        equipment: &["Navigator's Tools", "2 Pouches", "Traveler's Clothes"],
+        equipment_gold: 22,
+    },
+    Background {
+        name: "Noble",
+        abilities: [Ability::Strength, Ability::Intelligence, Ability::Charisma],
+        feat: "Skilled",
+        skills: [Skill::History, Skill::Persuasion],
+        tool: BackgroundTool::Choice("Gaming Set"),
+        equipment: &["Gaming Set", "Fine Clothes", "Perfume"],
+        equipment_gold: 29,
+    },
+    Background {
+        name: "Sage",
+        abilities: [
+            Ability::Constitution,
+            Ability::Intelligence,
+            Ability::Wisdom,
+        ],
+        feat: "Magic Initiate",
+        skills: [Skill::Arcana, Skill::History],
+        tool: BackgroundTool::Fixed("Calligrapher's Supplies"),
+        equipment: &[
+            "Quarterstaff",
+            "Calligrapher's Supplies",
+            "Book (history)",
+            "Parchment (8 sheets)",
+            "Robe",
+        ],
+        equipment_gold: 8,
+    },
+    Background {
+        name: "Sailor",
+        abilities: [Ability::Strength, Ability::Dexterity, Ability::Wisdom],
+        feat: "Tavern Brawler",
+        skills: [Skill::Acrobatics, Skill::Perception],
+        tool: BackgroundTool::Fixed("Navigator's Tools"),
+        equipment: &["Dagger", "Navigator's Tools", "Rope", "Traveler's Clothes"],
+        equipment_gold: 20,
+    },
+    Background {
+        name: "Scribe",
+        abilities: [Ability::Dexterity, Ability::Intelligence, Ability::Wisdom],
+        feat: "Skilled",
+        skills: [Skill::Investigation, Skill::Perception],
+        tool: BackgroundTool::Fixed("Calligrapher's Supplies"),
+        equipment: &[
+            "Calligrapher's Supplies",
+            "Fine Clothes",
+            "Lamp",
+            "Oil (3 flasks)",
+            "Parchment (12 sheets)",
+        ],
+        equipment_gold: 23,
+    },
+    Background {
+        name: "Soldier",
+        abilities: [Ability::Strength, Ability::Dexterity, Ability::Constitution],
+        feat: "Savage Attacker",
+        skills: [Skill::Athletics, Skill::Intimidation],
+        tool: BackgroundTool::Choice("Gaming Set"),
+        equipment: &[
+            "Spear",
+            "Shortbow",
+            "20 Arrows",
+            "Gaming Set",
+            "Healer's Kit",
+            "Quiver",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 14,
+    },
+    Background {
+        name: "Wayfarer",
+        abilities: [Ability::Dexterity, Ability::Wisdom, Ability::Charisma],
+        feat: "Lucky",
+        skills: [Skill::Insight, Skill::Stealth],
+        tool: BackgroundTool::Fixed("Thieves' Tools"),
+        equipment: &[
+            "2 Daggers",
+            "Thieves' Tools",
+            "Gaming Set",
+            "Bedroll",
+            "2 Pouches",
+            "Traveler's Clothes",
+        ],
+        equipment_gold: 16,
+    },
+];
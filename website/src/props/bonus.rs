@@ -0,0 +1,60 @@
+use std::fmt;
+
+use super::{Ability, Class, Condition, Species};
+
+/// Where a contribution to a derived value comes from.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BonusSource {
+    Ability(Ability),
+    HitDice,
+    Species(Species),
+    Class(Class),
+    Background(String),
+    Feat(String),
+    Proficiency,
+    Condition(Condition),
+    Exhaustion,
+    Unarmored,
+    Equipment(String),
+}
+
+impl fmt::Display for BonusSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BonusSource::Ability(ability) => write!(f, "{ability:?} modifier"),
+            BonusSource::HitDice => f.write_str("Hit dice"),
+            BonusSource::Species(species) => write!(f, "{species:?}"),
+            BonusSource::Class(class) => write!(f, "{class:?}"),
+            BonusSource::Background(name) => write!(f, "{name} background"),
+            BonusSource::Feat(name) => write!(f, "{name} feat"),
+            BonusSource::Proficiency => f.write_str("Proficiency"),
+            BonusSource::Condition(condition) => write!(f, "{condition:?}"),
+            BonusSource::Exhaustion => f.write_str("Exhaustion"),
+            BonusSource::Unarmored => f.write_str("Unarmored"),
+            BonusSource::Equipment(name) => f.write_str(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Bonus {
+    pub source: BonusSource,
+    pub value: i32,
+}
+
+impl Bonus {
+    pub fn new(source: BonusSource, value: i32) -> Self {
+        Bonus { source, value }
+    }
+}
+
+impl fmt::Display for Bonus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+} {}", self.value, self.source)
+    }
+}
+
+/// Sum of all bonuses.
+pub fn total(bonuses: &[Bonus]) -> i32 {
+    bonuses.iter().map(|bonus| bonus.value).sum()
+}
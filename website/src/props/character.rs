@@ -1,9 +1,14 @@
 use dioxus::prelude::*;
 
 use crate::Route;
-use std::{collections::HashMap, str::FromStr};
+use std::{collections::HashMap, fmt, str::FromStr};
 
-use super::Equipment;
+use super::{
+    ability_modifier, proficiency_bonus, total, Ability, ActiveCondition, ArmorCategory, Bonus,
+    BonusSource, ChosenBackground, ChosenFeat, Class, Equipment, EquipmentProperties, FeatEffect,
+    LevelRecord, Proficiency, SpellSlots, SpentResource, ABILITY_SCORE_RANGE,
+    EXHAUSTION_SPEED_PENALTY, MEDIUM_ARMOR_MAX_DEX,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Morality {
@@ -28,6 +33,10 @@ impl FromStr for Morality {
     }
 }
 
+impl Morality {
+    pub const ALL: [Morality; 3] = [Morality::Good, Morality::Neutral, Morality::Evil];
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum Order {
     Lawful,
@@ -51,6 +60,10 @@ impl FromStr for Order {
     }
 }
 
+impl Order {
+    pub const ALL: [Order; 3] = [Order::Lawful, Order::Neutral, Order::Chaotic];
+}
+
 mod alignment {
     use std::{fmt, str::FromStr};
 
@@ -146,22 +159,54 @@ pub enum Species {
     Tiefling,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
-pub enum Class {
-    Artificer,
-    Barbarian,
-    Bard,
-    Cleric,
-    Druid,
-    Fighter,
-    Monk,
-    Paladin,
-    Ranger,
-    Rogue,
-    Sorcerer,
-    Wizard,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidSpecies;
+
+impl FromStr for Species {
+    type Err = InvalidSpecies;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Human" => Ok(Species::Human),
+            "Elf" => Ok(Species::Elf),
+            "Dwarf" => Ok(Species::Dwarf),
+            "Orc" => Ok(Species::Orc),
+            "Halfling" => Ok(Species::Halfling),
+            "Gnome" => Ok(Species::Gnome),
+            "Tiefling" => Ok(Species::Tiefling),
+            _ => Err(InvalidSpecies),
+        }
+    }
+}
+
+impl Species {
+    pub const ALL: [Species; 7] = [
+        Species::Human,
+        Species::Elf,
+        Species::Dwarf,
+        Species::Orc,
+        Species::Halfling,
+        Species::Gnome,
+        Species::Tiefling,
+    ];
 }
 
+/// Base ability score outside of [`ABILITY_SCORE_RANGE`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidAbilityScore(pub u32);
+
+impl fmt::Display for InvalidAbilityScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Ability score {} is outside of {}-{}",
+            self.0, ABILITY_SCORE_RANGE.0, ABILITY_SCORE_RANGE.1
+        )
+    }
+}
+
+impl std::error::Error for InvalidAbilityScore {}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum Sex {
     Male,
@@ -173,14 +218,52 @@ pub struct Character {
     pub name: String,
     pub species: Species,
 
-    #[serde(skip_serializing_if = "HashMap::is_empty", default)]
-    classes: HashMap<Class, u32>,
+    #[serde(default)]
+    pub(super) classes: HashMap<Class, u32>,
+
+    #[serde(default)]
+    pub(super) levels: Vec<LevelRecord>,
+
+    /// Feats granted outside of level-ups.
+    #[serde(default)]
+    pub(super) feats: Vec<ChosenFeat>,
 
     #[serde(with = "alignment")]
     pub alignment: (Order, Morality),
 
+    /// Free-text origin, for story-only origins or details beyond the background.
     pub origin: String,
 
+    #[serde(default)]
+    pub(super) background: Option<ChosenBackground>,
+
+    /// Expended uses of class resources.
+    #[serde(default)]
+    pub(super) resources_spent: Vec<SpentResource>,
+
+    /// Hit points lost, so that changes of the maximum keep the damage.
+    #[serde(default)]
+    pub(super) damage: u32,
+
+    #[serde(default)]
+    pub(super) hit_dice_spent: HashMap<Class, u32>,
+
+    #[serde(default)]
+    pub(super) spell_slots_spent: SpellSlots,
+
+    #[serde(default)]
+    pub(super) pact_slots_spent: u32,
+
+    #[serde(default)]
+    pub(super) exhaustion: u32,
+
+    #[serde(default)]
+    pub(super) conditions: Vec<ActiveCondition>,
+
+    /// Worn and wielded items.
+    #[serde(default)]
+    pub(super) equipment: Vec<Equipment>,
+
     pub r#str: u32,
     pub dex: u32,
     pub con: u32,
@@ -217,8 +300,19 @@ impl Character {
             name: "<unnamed>".to_string(),
             species: Species::Human,
             classes: HashMap::new(),
+            levels: Vec::new(),
+            feats: Vec::new(),
             alignment: (Order::Neutral, Morality::Neutral),
             origin: "<not chosen>".to_string(),
+            background: None,
+            resources_spent: Vec::new(),
+            damage: 0,
+            hit_dice_spent: HashMap::new(),
+            spell_slots_spent: SpellSlots::default(),
+            pact_slots_spent: 0,
+            exhaustion: 0,
+            conditions: Vec::new(),
+            equipment: Vec::new(),
             r#str: 10,
             dex: 10,
             con: 10,
@@ -243,4 +337,291 @@ impl Character {
     pub fn total_level(&self) -> u32 {
         self.classes.values().sum::<u32>()
     }
+
+    /// Class levels in the order of [`Class::ALL`].
+    pub fn classes(&self) -> impl Iterator<Item = (Class, u32)> + '_ {
+        Class::ALL
+            .into_iter()
+            .filter_map(|class| Some((class, *self.classes.get(&class)?)))
+    }
+
+    pub fn class_level(&self, class: Class) -> u32 {
+        self.classes.get(&class).copied().unwrap_or(0)
+    }
+
+    /// Ability score before any improvements.
+    pub fn base_ability_score(&self, ability: Ability) -> u32 {
+        match ability {
+            Ability::Strength => self.r#str,
+            Ability::Dexterity => self.dex,
+            Ability::Constitution => self.con,
+            Ability::Intelligence => self.int,
+            Ability::Wisdom => self.wis,
+            Ability::Charisma => self.cha,
+        }
+    }
+
+    pub fn set_base_ability_score(
+        &mut self,
+        ability: Ability,
+        score: u32,
+    ) -> Result<(), InvalidAbilityScore> {
+        if !(ABILITY_SCORE_RANGE.0..=ABILITY_SCORE_RANGE.1).contains(&score) {
+            return Err(InvalidAbilityScore(score));
+        }
+
+        let field = match ability {
+            Ability::Strength => &mut self.r#str,
+            Ability::Dexterity => &mut self.dex,
+            Ability::Constitution => &mut self.con,
+            Ability::Intelligence => &mut self.int,
+            Ability::Wisdom => &mut self.wis,
+            Ability::Charisma => &mut self.cha,
+        };
+        *field = score;
+        Ok(())
+    }
+
+    /// Ability score with all improvements.
+    pub fn ability_score(&self, ability: Ability) -> u32 {
+        let bonus = total(&self.ability_score_bonuses(ability));
+        (self.base_ability_score(ability) as i32 + bonus).max(0) as u32
+    }
+
+    /// Improvements of the ability score and where they come from.
+    pub fn ability_score_bonuses(&self, ability: Ability) -> Vec<Bonus> {
+        let background = self.background.iter().filter_map(|background| {
+            let increase = background
+                .abilities
+                .iter()
+                .filter(|&&a| a == ability)
+                .count() as i32;
+            (increase > 0)
+                .then(|| Bonus::new(BonusSource::Background(background.name.clone()), increase))
+        });
+        let feats = self.feats().filter_map(|(_, feat)| {
+            let increase = feat.abilities().filter(|&a| a == ability).count() as i32;
+            (increase > 0).then(|| Bonus::new(BonusSource::Feat(feat.name.clone()), increase))
+        });
+
+        background.chain(feats).collect()
+    }
+
+    pub fn ability_modifier(&self, ability: Ability) -> i32 {
+        ability_modifier(self.ability_score(ability))
+    }
+
+    pub fn proficiency_bonus(&self) -> i32 {
+        proficiency_bonus(self.total_level())
+    }
+
+    /// Contributions to maximum hit points.
+    ///
+    /// Each level grants at least 1 hit point regardless of the Constitution modifier.
+    pub fn hit_point_bonuses(&self) -> Vec<Bonus> {
+        let con = self.ability_modifier(Ability::Constitution);
+        let level = self.levels.len() as i32;
+
+        let hit_dice = self
+            .levels
+            .iter()
+            .map(|level| level.hit_points as i32)
+            .sum::<i32>();
+        let con_total = self
+            .levels
+            .iter()
+            .map(|level| con.max(1 - level.hit_points as i32))
+            .sum::<i32>();
+
+        let mut bonuses = vec![
+            Bonus::new(BonusSource::HitDice, hit_dice),
+            Bonus::new(BonusSource::Ability(Ability::Constitution), con_total),
+        ];
+        bonuses.extend(self.feat_effect_bonuses(|effect| match effect {
+            FeatEffect::HitPoints(hp) => hp as i32,
+            FeatEffect::HitPointsPerLevel(hp) => hp as i32 * level,
+            _ => 0,
+        }));
+        bonuses
+    }
+
+    pub fn max_hit_points(&self) -> u32 {
+        total(&self.hit_point_bonuses()).max(0) as u32
+    }
+
+    pub fn initiative_bonuses(&self) -> Vec<Bonus> {
+        let proficiency = self.proficiency_bonus();
+
+        let mut bonuses = vec![Bonus::new(
+            BonusSource::Ability(Ability::Dexterity),
+            self.ability_modifier(Ability::Dexterity),
+        )];
+        bonuses.extend(self.feat_effect_bonuses(|effect| match effect {
+            FeatEffect::InitiativeProficiency => proficiency,
+            _ => 0,
+        }));
+        bonuses.extend(self.exhaustion_d20_penalty());
+        bonuses
+    }
+
+    /// Walking speed in feet.
+    ///
+    /// Conditions that set the speed to 0 cancel out all other contributions.
+    pub fn speed_bonuses(&self) -> Vec<Bonus> {
+        let mut bonuses = vec![Bonus::new(BonusSource::Species(self.species), 30)];
+        bonuses.extend(self.feat_effect_bonuses(|effect| match effect {
+            FeatEffect::Speed(speed) => speed as i32,
+            _ => 0,
+        }));
+        if self.exhaustion > 0 {
+            bonuses.push(Bonus::new(
+                BonusSource::Exhaustion,
+                -EXHAUSTION_SPEED_PENALTY * self.exhaustion as i32,
+            ));
+        }
+
+        let speed_zero = self
+            .effective_conditions()
+            .into_iter()
+            .find(|condition| condition.speed_zero());
+        if let Some(condition) = speed_zero {
+            let speed = total(&bonuses).max(0);
+            bonuses.push(Bonus::new(BonusSource::Condition(condition), -speed));
+        }
+        bonuses
+    }
+
+    pub fn speed(&self) -> u32 {
+        total(&self.speed_bonuses()).max(0) as u32
+    }
+
+    /// Armor class from the worn armor and shield, or from Unarmored Defense
+    /// when no armor is worn.
+    pub fn armor_class_bonuses(&self) -> Vec<Bonus> {
+        let dex = self.ability_modifier(Ability::Dexterity);
+        let armor = self
+            .equipment
+            .iter()
+            .find_map(|item| match item.properties() {
+                EquipmentProperties::Armor(armor) => Some((item.name(), armor)),
+                _ => None,
+            });
+        let shield = self
+            .equipment
+            .iter()
+            .find_map(|item| match item.properties() {
+                EquipmentProperties::Shield(shield) => Some((item.name(), shield)),
+                _ => None,
+            });
+
+        let mut bonuses = Vec::new();
+        match armor {
+            Some((name, armor)) => {
+                bonuses.push(Bonus::new(
+                    BonusSource::Equipment(name.to_owned()),
+                    armor.armor_class() as i32,
+                ));
+                let dex = match armor.category() {
+                    ArmorCategory::Light => dex,
+                    ArmorCategory::Medium => dex.min(MEDIUM_ARMOR_MAX_DEX),
+                    ArmorCategory::Heavy => 0,
+                };
+                if dex != 0 {
+                    bonuses.push(Bonus::new(BonusSource::Ability(Ability::Dexterity), dex));
+                }
+            }
+            None => {
+                bonuses.push(Bonus::new(BonusSource::Unarmored, 10));
+                bonuses.push(Bonus::new(BonusSource::Ability(Ability::Dexterity), dex));
+                if self.class_level(Class::Barbarian) > 0 {
+                    bonuses.push(Bonus::new(
+                        BonusSource::Ability(Ability::Constitution),
+                        self.ability_modifier(Ability::Constitution),
+                    ));
+                } else if self.class_level(Class::Monk) > 0 && shield.is_none() {
+                    bonuses.push(Bonus::new(
+                        BonusSource::Ability(Ability::Wisdom),
+                        self.ability_modifier(Ability::Wisdom),
+                    ));
+                }
+            }
+        }
+        if let Some((name, shield)) = shield {
+            bonuses.push(Bonus::new(
+                BonusSource::Equipment(name.to_owned()),
+                shield.armor_class() as i32,
+            ));
+        }
+        bonuses
+    }
+
+    pub fn armor_class(&self) -> u32 {
+        total(&self.armor_class_bonuses()).max(0) as u32
+    }
+
+    /// All proficiencies of the character and where they come from.
+    pub fn proficiencies(&self) -> Vec<(Proficiency, BonusSource)> {
+        let mut proficiencies = Vec::new();
+
+        // Classes taken by multiclassing grant no saving throws.
+        if let Some(first) = self.levels.first() {
+            for ability in first.class.saving_throws() {
+                proficiencies.push((
+                    Proficiency::SavingThrow(ability),
+                    BonusSource::Class(first.class),
+                ));
+            }
+        }
+
+        for (class, _) in self.classes() {
+            for &category in class.armor_training() {
+                proficiencies.push((Proficiency::Armor(category), BonusSource::Class(class)));
+            }
+            if class.shield_training() {
+                proficiencies.push((Proficiency::Shields, BonusSource::Class(class)));
+            }
+        }
+
+        if let Some(background) = &self.background {
+            let source = BonusSource::Background(background.name.clone());
+            for proficiency in self.background_proficiencies() {
+                proficiencies.push((proficiency, source.clone()));
+            }
+        }
+
+        for (_, chosen) in self.feats() {
+            let source = BonusSource::Feat(chosen.name.clone());
+            let effects = chosen.feat().map_or(&[][..], |feat| feat.effects);
+            for effect in effects {
+                let proficiency = match *effect {
+                    FeatEffect::Tool(tool) => Proficiency::Tool(tool.to_owned()),
+                    FeatEffect::ArmorTraining(category) => Proficiency::Armor(category),
+                    FeatEffect::ShieldTraining => Proficiency::Shields,
+                    FeatEffect::WeaponTraining(category) => Proficiency::Weapon(category),
+                    _ => continue,
+                };
+                proficiencies.push((proficiency, source.clone()));
+            }
+            for proficiency in chosen.proficiencies() {
+                proficiencies.push((proficiency.clone(), source.clone()));
+            }
+        }
+
+        proficiencies
+    }
+
+    /// Bonuses from feat effects, one per feat with a non-zero value.
+    fn feat_effect_bonuses(&self, value: impl Fn(FeatEffect) -> i32) -> Vec<Bonus> {
+        self.feats()
+            .filter_map(|(_, chosen)| {
+                let feat = chosen.feat()?;
+                let value = feat
+                    .effects
+                    .iter()
+                    .map(|&effect| value(effect))
+                    .sum::<i32>();
+                (value != 0).then(|| Bonus::new(BonusSource::Feat(chosen.name.clone()), value))
+            })
+            .collect()
+    }
 }
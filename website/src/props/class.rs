@@ -0,0 +1,521 @@
+use std::str::FromStr;
+
+use super::{Ability, ArmorCategory, Dice};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Class {
+    Artificer,
+    Barbarian,
+    Bard,
+    Cleric,
+    Druid,
+    Fighter,
+    Monk,
+    Paladin,
+    Ranger,
+    Rogue,
+    Sorcerer,
+    Warlock,
+    Wizard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidClass;
+
+impl FromStr for Class {
+    type Err = InvalidClass;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Artificer" => Ok(Class::Artificer),
+            "Barbarian" => Ok(Class::Barbarian),
+            "Bard" => Ok(Class::Bard),
+            "Cleric" => Ok(Class::Cleric),
+            "Druid" => Ok(Class::Druid),
+            "Fighter" => Ok(Class::Fighter),
+            "Monk" => Ok(Class::Monk),
+            "Paladin" => Ok(Class::Paladin),
+            "Ranger" => Ok(Class::Ranger),
+            "Rogue" => Ok(Class::Rogue),
+            "Sorcerer" => Ok(Class::Sorcerer),
+            "Warlock" => Ok(Class::Warlock),
+            "Wizard" => Ok(Class::Wizard),
+            _ => Err(InvalidClass),
+        }
+    }
+}
+
+/// Ability scores a character needs to multiclass into or out of a class.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MulticlassPrerequisite {
+    /// Every listed ability must be at least 13.
+    All(&'static [Ability]),
+
+    /// At least one of the listed abilities must be at least 13.
+    Any(&'static [Ability]),
+}
+
+/// Minimum ability score required by multiclass prerequisites.
+pub const MULTICLASS_MIN_SCORE: u32 = 13;
+
+impl MulticlassPrerequisite {
+    pub fn is_met(&self, score: impl Fn(Ability) -> u32) -> bool {
+        match *self {
+            MulticlassPrerequisite::All(abilities) => abilities
+                .iter()
+                .all(|&ability| score(ability) >= MULTICLASS_MIN_SCORE),
+            MulticlassPrerequisite::Any(abilities) => abilities
+                .iter()
+                .any(|&ability| score(ability) >= MULTICLASS_MIN_SCORE),
+        }
+    }
+}
+
+impl Class {
+    pub const ALL: [Class; 13] = [
+        Class::Artificer,
+        Class::Barbarian,
+        Class::Bard,
+        Class::Cleric,
+        Class::Druid,
+        Class::Fighter,
+        Class::Monk,
+        Class::Paladin,
+        Class::Ranger,
+        Class::Rogue,
+        Class::Sorcerer,
+        Class::Warlock,
+        Class::Wizard,
+    ];
+
+    pub fn hit_die(self) -> Dice {
+        match self {
+            Class::Barbarian => Dice::D12,
+            Class::Fighter | Class::Paladin | Class::Ranger => Dice::D10,
+            Class::Artificer
+            | Class::Bard
+            | Class::Cleric
+            | Class::Druid
+            | Class::Monk
+            | Class::Rogue
+            | Class::Warlock => Dice::D8,
+            Class::Sorcerer | Class::Wizard => Dice::D6,
+        }
+    }
+
+    pub fn multiclass_prerequisite(self) -> MulticlassPrerequisite {
+        use Ability::*;
+
+        match self {
+            Class::Artificer => MulticlassPrerequisite::All(&[Intelligence]),
+            Class::Barbarian => MulticlassPrerequisite::All(&[Strength]),
+            Class::Bard => MulticlassPrerequisite::All(&[Charisma]),
+            Class::Cleric => MulticlassPrerequisite::All(&[Wisdom]),
+            Class::Druid => MulticlassPrerequisite::All(&[Wisdom]),
+            Class::Fighter => MulticlassPrerequisite::Any(&[Strength, Dexterity]),
+            Class::Monk => MulticlassPrerequisite::All(&[Dexterity, Wisdom]),
+            Class::Paladin => MulticlassPrerequisite::All(&[Strength, Charisma]),
+            Class::Ranger => MulticlassPrerequisite::All(&[Dexterity, Wisdom]),
+            Class::Rogue => MulticlassPrerequisite::All(&[Dexterity]),
+            Class::Sorcerer => MulticlassPrerequisite::All(&[Charisma]),
+            Class::Warlock => MulticlassPrerequisite::All(&[Charisma]),
+            Class::Wizard => MulticlassPrerequisite::All(&[Intelligence]),
+        }
+    }
+
+    /// Saving throws the class is proficient in when it is the first class of the character.
+    pub fn saving_throws(self) -> [Ability; 2] {
+        use Ability::*;
+
+        match self {
+            Class::Artificer => [Constitution, Intelligence],
+            Class::Barbarian | Class::Fighter => [Strength, Constitution],
+            Class::Bard => [Dexterity, Charisma],
+            Class::Cleric | Class::Paladin | Class::Warlock => [Wisdom, Charisma],
+            Class::Druid | Class::Wizard => [Intelligence, Wisdom],
+            Class::Monk | Class::Ranger => [Strength, Dexterity],
+            Class::Rogue => [Dexterity, Intelligence],
+            Class::Sorcerer => [Constitution, Charisma],
+        }
+    }
+
+    pub fn armor_training(self) -> &'static [ArmorCategory] {
+        use ArmorCategory::*;
+
+        match self {
+            Class::Fighter | Class::Paladin => &[Light, Medium, Heavy],
+            Class::Artificer | Class::Barbarian | Class::Cleric | Class::Druid | Class::Ranger => {
+                &[Light, Medium]
+            }
+            Class::Bard | Class::Rogue | Class::Warlock => &[Light],
+            Class::Monk | Class::Sorcerer | Class::Wizard => &[],
+        }
+    }
+
+    pub fn shield_training(self) -> bool {
+        matches!(
+            self,
+            Class::Artificer
+                | Class::Barbarian
+                | Class::Cleric
+                | Class::Druid
+                | Class::Fighter
+                | Class::Paladin
+                | Class::Ranger
+        )
+    }
+
+    /// Returns true if the class grants an Ability Score Improvement or feat at the given class level.
+    pub fn grants_ability_score_improvement(self, level: u32) -> bool {
+        matches!(
+            (self, level),
+            (_, 4 | 8 | 12 | 16 | 19) | (Class::Fighter, 6 | 14) | (Class::Rogue, 10)
+        )
+    }
+
+    /// Class level at which the subclass is chosen.
+    pub fn subclass_level(self) -> u32 {
+        3
+    }
+
+    /// Returns true if the class learns or prepares new spells at the given class level.
+    pub fn learns_spells(self, level: u32) -> bool {
+        match self {
+            Class::Barbarian | Class::Fighter | Class::Monk | Class::Rogue => false,
+            _ => level >= 1,
+        }
+    }
+
+    /// Names of class features gained at the given class level.
+    ///
+    /// Ability Score Improvements, subclass choice and Epic Boons are choices
+    /// rather than features and are not listed here.
+    pub fn features(self, level: u32) -> &'static [&'static str] {
+        let table: &[&[&str]; 20] = match self {
+            Class::Artificer => &ARTIFICER_FEATURES,
+            Class::Barbarian => &BARBARIAN_FEATURES,
+            Class::Bard => &BARD_FEATURES,
+            Class::Cleric => &CLERIC_FEATURES,
+            Class::Druid => &DRUID_FEATURES,
+            Class::Fighter => &FIGHTER_FEATURES,
+            Class::Monk => &MONK_FEATURES,
+            Class::Paladin => &PALADIN_FEATURES,
+            Class::Ranger => &RANGER_FEATURES,
+            Class::Rogue => &ROGUE_FEATURES,
+            Class::Sorcerer => &SORCERER_FEATURES,
+            Class::Warlock => &WARLOCK_FEATURES,
+            Class::Wizard => &WIZARD_FEATURES,
+        };
+
+        match level {
+            1..=20 => table[level as usize - 1],
+            _ => &[],
+        }
+    }
+}
+
+const SUBCLASS_FEATURE: &str = "Subclass Feature";
+
+const ARTIFICER_FEATURES: [&[&str]; 20] = [
+    &["Magical Tinkering", "Spellcasting"],
+    &["Infuse Item"],
+    &["The Right Tool for the Job"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Tool Expertise"],
+    &["Flash of Genius"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Magic Item Adept"],
+    &["Spell-Storing Item"],
+    &[],
+    &[],
+    &["Magic Item Savant"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &["Magic Item Master"],
+    &[],
+    &["Soul of Artifice"],
+];
+
+const BARBARIAN_FEATURES: [&[&str]; 20] = [
+    &["Rage", "Unarmored Defense", "Weapon Mastery"],
+    &["Danger Sense", "Reckless Attack"],
+    &["Primal Knowledge"],
+    &[],
+    &["Extra Attack", "Fast Movement"],
+    &[SUBCLASS_FEATURE],
+    &["Feral Instinct", "Instinctive Pounce"],
+    &[],
+    &["Brutal Strike"],
+    &[SUBCLASS_FEATURE],
+    &["Relentless Rage"],
+    &[],
+    &["Improved Brutal Strike"],
+    &[SUBCLASS_FEATURE],
+    &["Persistent Rage"],
+    &[],
+    &["Improved Brutal Strike"],
+    &["Indomitable Might"],
+    &[],
+    &["Primal Champion"],
+];
+
+const BARD_FEATURES: [&[&str]; 20] = [
+    &["Bardic Inspiration", "Spellcasting"],
+    &["Expertise", "Jack of All Trades"],
+    &[],
+    &[],
+    &["Font of Inspiration"],
+    &[SUBCLASS_FEATURE],
+    &["Countercharm"],
+    &[],
+    &["Expertise"],
+    &["Magical Secrets"],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &[],
+    &["Superior Inspiration"],
+    &[],
+    &["Words of Creation"],
+];
+
+const CLERIC_FEATURES: [&[&str]; 20] = [
+    &["Spellcasting", "Divine Order"],
+    &["Channel Divinity"],
+    &[],
+    &[],
+    &["Sear Undead"],
+    &[SUBCLASS_FEATURE],
+    &["Blessed Strikes"],
+    &[],
+    &[],
+    &["Divine Intervention"],
+    &[],
+    &[],
+    &[],
+    &["Improved Blessed Strikes"],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &["Greater Divine Intervention"],
+];
+
+const DRUID_FEATURES: [&[&str]; 20] = [
+    &["Spellcasting", "Druidic", "Primal Order"],
+    &["Wild Shape", "Wild Companion"],
+    &[],
+    &[],
+    &["Wild Resurgence"],
+    &[SUBCLASS_FEATURE],
+    &["Elemental Fury"],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Improved Elemental Fury"],
+    &[],
+    &[],
+    &["Beast Spells"],
+    &[],
+    &["Archdruid"],
+];
+
+const FIGHTER_FEATURES: [&[&str]; 20] = [
+    &["Fighting Style", "Second Wind", "Weapon Mastery"],
+    &["Action Surge", "Tactical Mind"],
+    &[],
+    &[],
+    &["Extra Attack", "Tactical Shift"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Indomitable", "Tactical Master"],
+    &[SUBCLASS_FEATURE],
+    &["Two Extra Attacks"],
+    &[],
+    &["Indomitable", "Studied Attacks"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Action Surge", "Indomitable"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Three Extra Attacks"],
+];
+
+const MONK_FEATURES: [&[&str]; 20] = [
+    &["Martial Arts", "Unarmored Defense"],
+    &["Monk's Focus", "Unarmored Movement", "Uncanny Metabolism"],
+    &["Deflect Attacks"],
+    &["Slow Fall"],
+    &["Extra Attack", "Stunning Strike"],
+    &["Empowered Strikes", SUBCLASS_FEATURE],
+    &["Evasion"],
+    &[],
+    &["Acrobatic Movement"],
+    &["Heightened Focus", "Self-Restoration"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Deflect Energy"],
+    &["Disciplined Survivor"],
+    &["Perfect Focus"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Superior Defense"],
+    &[],
+    &["Body and Mind"],
+];
+
+const PALADIN_FEATURES: [&[&str]; 20] = [
+    &["Lay On Hands", "Spellcasting", "Weapon Mastery"],
+    &["Fighting Style", "Paladin's Smite"],
+    &["Channel Divinity"],
+    &[],
+    &["Extra Attack", "Faithful Steed"],
+    &["Aura of Protection"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Abjure Foes"],
+    &["Aura of Courage"],
+    &["Radiant Strikes"],
+    &[],
+    &[],
+    &["Restoring Touch"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &["Aura Expansion"],
+    &[],
+    &[SUBCLASS_FEATURE],
+];
+
+const RANGER_FEATURES: [&[&str]; 20] = [
+    &["Spellcasting", "Favored Enemy", "Weapon Mastery"],
+    &["Deft Explorer", "Fighting Style"],
+    &[],
+    &[],
+    &["Extra Attack"],
+    &["Roving"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Expertise"],
+    &["Tireless"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Relentless Hunter"],
+    &["Nature's Veil"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Precise Hunter"],
+    &["Feral Senses"],
+    &[],
+    &["Foe Slayer"],
+];
+
+const ROGUE_FEATURES: [&[&str]; 20] = [
+    &[
+        "Expertise",
+        "Sneak Attack",
+        "Thieves' Cant",
+        "Weapon Mastery",
+    ],
+    &["Cunning Action"],
+    &["Steady Aim"],
+    &[],
+    &["Cunning Strike", "Uncanny Dodge"],
+    &["Expertise"],
+    &["Evasion", "Reliable Talent"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Improved Cunning Strike"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Devious Strikes"],
+    &["Slippery Mind"],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &["Elusive"],
+    &[],
+    &["Stroke of Luck"],
+];
+
+const SORCERER_FEATURES: [&[&str]; 20] = [
+    &["Spellcasting", "Innate Sorcery"],
+    &["Font of Magic", "Metamagic"],
+    &[],
+    &[],
+    &["Sorcerous Restoration"],
+    &[SUBCLASS_FEATURE],
+    &["Sorcery Incarnate"],
+    &[],
+    &[],
+    &["Metamagic"],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &["Metamagic"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &["Arcane Apotheosis"],
+];
+
+const WARLOCK_FEATURES: [&[&str]; 20] = [
+    &["Eldritch Invocations", "Pact Magic"],
+    &["Magical Cunning"],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &["Contact Patron"],
+    &[SUBCLASS_FEATURE],
+    &["Mystic Arcanum"],
+    &[],
+    &["Mystic Arcanum"],
+    &[SUBCLASS_FEATURE],
+    &["Mystic Arcanum"],
+    &[],
+    &["Mystic Arcanum"],
+    &[],
+    &[],
+    &["Eldritch Master"],
+];
+
+const WIZARD_FEATURES: [&[&str]; 20] = [
+    &["Spellcasting", "Ritual Adept", "Arcane Recovery"],
+    &["Scholar"],
+    &[],
+    &[],
+    &["Memorize Spell"],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &[],
+    &[SUBCLASS_FEATURE],
+    &[],
+    &[],
+    &[],
+    &["Spell Mastery"],
+    &[],
+    &["Signature Spells"],
+];
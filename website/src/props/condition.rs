@@ -0,0 +1,204 @@
+use std::{fmt, str::FromStr};
+
+use super::{Ability, Character, D20Test, RollModifier};
+
+/// Highest exhaustion level; the character dies when reaching it.
+pub const MAX_EXHAUSTION: u32 = 6;
+
+/// Penalty to d20 tests per exhaustion level.
+pub const EXHAUSTION_D20_PENALTY: i32 = 2;
+
+/// Speed lost per exhaustion level, in feet.
+pub const EXHAUSTION_SPEED_PENALTY: i32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Condition {
+    Blinded,
+    Charmed,
+    Frightened,
+    Grappled,
+    Incapacitated,
+    Invisible,
+    Paralyzed,
+    Petrified,
+    Poisoned,
+    Prone,
+    Restrained,
+    Stunned,
+    Unconscious,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidCondition;
+
+impl FromStr for Condition {
+    type Err = InvalidCondition;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Blinded" => Ok(Condition::Blinded),
+            "Charmed" => Ok(Condition::Charmed),
+            "Frightened" => Ok(Condition::Frightened),
+            "Grappled" => Ok(Condition::Grappled),
+            "Incapacitated" => Ok(Condition::Incapacitated),
+            "Invisible" => Ok(Condition::Invisible),
+            "Paralyzed" => Ok(Condition::Paralyzed),
+            "Petrified" => Ok(Condition::Petrified),
+            "Poisoned" => Ok(Condition::Poisoned),
+            "Prone" => Ok(Condition::Prone),
+            "Restrained" => Ok(Condition::Restrained),
+            "Stunned" => Ok(Condition::Stunned),
+            "Unconscious" => Ok(Condition::Unconscious),
+            _ => Err(InvalidCondition),
+        }
+    }
+}
+
+impl Condition {
+    pub const ALL: [Condition; 13] = [
+        Condition::Blinded,
+        Condition::Charmed,
+        Condition::Frightened,
+        Condition::Grappled,
+        Condition::Incapacitated,
+        Condition::Invisible,
+        Condition::Paralyzed,
+        Condition::Petrified,
+        Condition::Poisoned,
+        Condition::Prone,
+        Condition::Restrained,
+        Condition::Stunned,
+        Condition::Unconscious,
+    ];
+
+    /// Conditions that come with this one, such as Incapacitated for Stunned.
+    pub fn implied(self) -> &'static [Condition] {
+        match self {
+            Condition::Paralyzed | Condition::Petrified | Condition::Stunned => {
+                &[Condition::Incapacitated]
+            }
+            Condition::Unconscious => &[Condition::Incapacitated, Condition::Prone],
+            _ => &[],
+        }
+    }
+
+    pub fn speed_zero(self) -> bool {
+        matches!(
+            self,
+            Condition::Grappled
+                | Condition::Paralyzed
+                | Condition::Petrified
+                | Condition::Restrained
+                | Condition::Unconscious
+        )
+    }
+
+    /// Effect of the condition on the character's own d20 test.
+    ///
+    /// Effects that depend on the target or the source, such as a Frightened
+    /// creature seeing the source of its fear, are assumed to apply.
+    pub fn roll_modifier(self, test: D20Test) -> Option<RollModifier> {
+        use D20Test::*;
+
+        let strength_or_dexterity =
+            |ability| matches!(ability, Ability::Strength | Ability::Dexterity);
+
+        match (self, test) {
+            (Condition::Blinded | Condition::Prone | Condition::Grappled, AttackRoll(_))
+            | (
+                Condition::Frightened | Condition::Poisoned,
+                AttackRoll(_) | AbilityCheck(_) | Initiative,
+            )
+            | (Condition::Restrained, AttackRoll(_) | SavingThrow(Ability::Dexterity))
+            | (Condition::Incapacitated, Initiative) => Some(RollModifier::Disadvantage),
+            (Condition::Invisible, AttackRoll(_) | Initiative) => Some(RollModifier::Advantage),
+            (
+                Condition::Paralyzed
+                | Condition::Petrified
+                | Condition::Stunned
+                | Condition::Unconscious,
+                SavingThrow(ability),
+            ) if strength_or_dexterity(ability) => Some(RollModifier::AutomaticFailure),
+            _ => None,
+        }
+    }
+}
+
+/// Condition affecting the character.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ActiveCondition {
+    pub condition: Condition,
+
+    /// What caused the condition, such as a spell or a creature.
+    #[serde(default)]
+    pub source: Option<String>,
+
+    /// Rounds left; the condition lasts until removed when absent.
+    #[serde(default)]
+    pub rounds: Option<u32>,
+}
+
+impl fmt::Display for ActiveCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.condition)?;
+        if let Some(source) = &self.source {
+            write!(f, " ({source})")?;
+        }
+        if let Some(rounds) = self.rounds {
+            write!(f, ", {rounds} rounds")?;
+        }
+        Ok(())
+    }
+}
+
+impl Character {
+    pub fn conditions(&self) -> &[ActiveCondition] {
+        &self.conditions
+    }
+
+    /// Conditions in effect, including the implied ones.
+    pub fn effective_conditions(&self) -> Vec<Condition> {
+        let mut conditions = Vec::new();
+        for active in &self.conditions {
+            for &condition in std::iter::once(&active.condition).chain(active.condition.implied()) {
+                if !conditions.contains(&condition) {
+                    conditions.push(condition);
+                }
+            }
+        }
+        conditions
+    }
+
+    pub fn add_condition(&mut self, condition: ActiveCondition) {
+        self.conditions.push(condition);
+    }
+
+    pub fn remove_condition(&mut self, index: usize) -> Option<ActiveCondition> {
+        (index < self.conditions.len()).then(|| self.conditions.remove(index))
+    }
+
+    /// Counts down timed conditions at the end of a round, removing the expired ones.
+    pub fn end_round(&mut self) -> Vec<ActiveCondition> {
+        for rounds in self
+            .conditions
+            .iter_mut()
+            .filter_map(|active| active.rounds.as_mut())
+        {
+            *rounds = rounds.saturating_sub(1);
+        }
+
+        let (expired, active) = std::mem::take(&mut self.conditions)
+            .into_iter()
+            .partition(|active| active.rounds == Some(0));
+        self.conditions = active;
+        expired
+    }
+
+    pub fn exhaustion(&self) -> u32 {
+        self.exhaustion
+    }
+
+    pub fn set_exhaustion(&mut self, level: u32) {
+        self.exhaustion = level.min(MAX_EXHAUSTION);
+    }
+}
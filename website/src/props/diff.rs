@@ -0,0 +1,189 @@
+use serde_json::Value;
+
+use super::{Character, FieldChange};
+
+/// Labels of the fields of the character document that are not a word with its first
+/// letter capitalized.
+const FIELD_LABELS: &[(&str, &str)] = &[
+    ("str", "Strength"),
+    ("dex", "Dexterity"),
+    ("con", "Constitution"),
+    ("int", "Intelligence"),
+    ("wis", "Wisdom"),
+    ("cha", "Charisma"),
+    ("damage", "Damage taken"),
+    ("hit_dice_spent", "Spent hit dice"),
+    ("spell_slots_spent", "Spent spell slots"),
+    ("pact_slots_spent", "Spent pact slots"),
+    ("resources_spent", "Spent resources"),
+    ("levels", "Level"),
+    ("feats", "Feat"),
+    ("conditions", "Condition"),
+];
+
+impl Character {
+    /// Every field that differs between the characters, with `old` from `self`
+    /// and `new` from `other`.
+    ///
+    /// Unlike the changes recorded in the journal, lists of different lengths are
+    /// compared item by item, so that an added item is a change of its own.
+    pub fn diff(&self, other: &Character) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        diff_values(
+            String::new(),
+            Some(&self.to_json_value()),
+            Some(&other.to_json_value()),
+            ArrayDiff::ItemByItem,
+            &mut changes,
+        );
+        changes
+    }
+}
+
+impl FieldChange {
+    /// Readable name of the changed field, such as `Level 2 › Hit points`.
+    pub fn label(&self) -> String {
+        let mut parts = Vec::<String>::new();
+        let mut list = "";
+        for part in self.path.split('.') {
+            let mut pieces = part.split('[');
+            let key = pieces.next().unwrap_or_default();
+            if !key.is_empty() {
+                parts.push(field_label(key));
+                list = key;
+            }
+            for index in pieces {
+                let number = index
+                    .strip_suffix(']')
+                    .and_then(|index| index.parse::<usize>().ok())
+                    .map_or(0, |index| index + 1);
+                match (list, parts.last_mut()) {
+                    ("spell_slots_spent", Some(_)) => parts.push(format!("level {number}")),
+                    (_, Some(last)) => *last = format!("{last} {number}"),
+                    (_, None) => parts.push(number.to_string()),
+                }
+            }
+        }
+        parts.join(" › ")
+    }
+}
+
+fn field_label(key: &str) -> String {
+    if let Some((_, label)) = FIELD_LABELS.iter().find(|(field, _)| *field == key) {
+        return (*label).to_owned();
+    }
+    let text = key.replace('_', " ");
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => text,
+    }
+}
+
+/// Readable text of a field value, `none` when the field is absent.
+pub fn value_text(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => "none".to_owned(),
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+    }
+}
+
+/// How arrays of different lengths are compared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(super) enum ArrayDiff {
+    /// As one change of the whole array, which the journal can set back in one step.
+    Whole,
+
+    /// Item by item, so that an added item is a change of its own.
+    ItemByItem,
+}
+
+/// Pushes a change for every field at or below `path` that differs between the values.
+///
+/// Objects are compared key by key and arrays of the same length item by item.
+pub(super) fn diff_values(
+    path: String,
+    old: Option<&Value>,
+    new: Option<&Value>,
+    arrays: ArrayDiff,
+    changes: &mut Vec<FieldChange>,
+) {
+    match (old, new) {
+        (Some(Value::Object(old)), Some(Value::Object(new))) => {
+            let added = new.keys().filter(|key| !old.contains_key(*key));
+            for key in old.keys().chain(added) {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                diff_values(path, old.get(key), new.get(key), arrays, changes);
+            }
+        }
+        (Some(Value::Array(old)), Some(Value::Array(new)))
+            if old.len() == new.len() || arrays == ArrayDiff::ItemByItem =>
+        {
+            for index in 0..old.len().max(new.len()) {
+                diff_values(
+                    format!("{path}[{index}]"),
+                    old.get(index),
+                    new.get(index),
+                    arrays,
+                    changes,
+                );
+            }
+        }
+        (old, new) if old != new => changes.push(FieldChange {
+            path,
+            old: old.cloned(),
+            new: new.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::props::Ability;
+
+    fn tordek() -> Character {
+        Character::from_json(include_str!("../../tests/fixtures/character_v1.json")).unwrap()
+    }
+
+    fn labels(changes: &[FieldChange]) -> Vec<String> {
+        changes.iter().map(FieldChange::label).collect()
+    }
+
+    #[test]
+    fn same_character_has_no_differences() {
+        assert!(tordek().diff(&tordek()).is_empty());
+    }
+
+    #[test]
+    fn nested_fields_are_labelled() {
+        let old = tordek();
+        let mut new = old.clone();
+        new.set_base_ability_score(Ability::Strength, 15).unwrap();
+        new.classes.insert(crate::props::Class::Fighter, 4);
+        new.levels[1].hit_points = 7;
+        new.spell_slots_spent[0] = 0;
+        new.conditions.clear();
+
+        let changes = old.diff(&new);
+        assert_eq!(
+            labels(&changes),
+            [
+                "Classes › Fighter",
+                "Condition 1",
+                "Level 2 › Hit points",
+                "Spent spell slots › level 1",
+                "Strength",
+            ]
+        );
+        assert_eq!(changes[1].new, None);
+        assert_eq!(value_text(changes[0].old.as_ref()), "3");
+        assert_eq!(value_text(changes[1].new.as_ref()), "none");
+    }
+}
@@ -1,5 +1,8 @@
 use crate::props::RollModifier;
 
+/// Highest Dexterity modifier added to the armor class in medium armor.
+pub const MEDIUM_ARMOR_MAX_DEX: i32 = 2;
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct Armor {
     name: String,
@@ -18,6 +21,17 @@ pub struct ArmorProperties {
     stealth: Option<RollModifier>,
 }
 
+impl ArmorProperties {
+    pub fn category(&self) -> ArmorCategory {
+        self.category
+    }
+
+    /// Base armor class, before the Dexterity modifier.
+    pub fn armor_class(&self) -> u32 {
+        self.armor_class
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum ArmorCategory {
     Light,
@@ -39,3 +53,10 @@ pub struct Shield {
 pub struct ShieldProperties{
     armor_class: u32,
 }
+
+impl ShieldProperties {
+    /// Bonus to the armor class while the shield is wielded.
+    pub fn armor_class(&self) -> u32 {
+        self.armor_class
+    }
+}
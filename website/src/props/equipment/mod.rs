@@ -12,6 +12,16 @@ pub struct Equipment {
     properties: EquipmentProperties,
 }
 
+impl Equipment {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn properties(&self) -> &EquipmentProperties {
+        &self.properties
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum EquipmentProperties {
     Weapon(WeaponProperties),
@@ -0,0 +1,693 @@
+use std::fmt;
+
+use super::{
+    Ability, ArmorCategory, Character, Proficiency, ProficiencyKind, WeaponCategory,
+    MAX_ABILITY_SCORE,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum FeatCategory {
+    Origin,
+    General,
+    FightingStyle,
+    EpicBoon,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatPrerequisite {
+    /// Minimum total character level.
+    Level(u32),
+
+    /// At least one of the abilities must have a score of 13 or higher.
+    Ability(&'static [Ability]),
+
+    /// Class feature the character must have, such as "Fighting Style".
+    ClassFeature(&'static str),
+
+    /// Spellcasting or Pact Magic feature.
+    Spellcasting,
+
+    ArmorTraining(ArmorCategory),
+}
+
+/// Minimum ability score required by feat prerequisites.
+pub const FEAT_MIN_SCORE: u32 = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatEffect {
+    /// Increase abilities by 1 per pick, `count` picks among `abilities`, but not above `max`.
+    ///
+    /// The same ability may be picked more than once.
+    AbilityIncrease {
+        count: u32,
+        abilities: &'static [Ability],
+        max: u32,
+    },
+
+    /// Proficiencies picked by the player.
+    ProficiencyChoice {
+        count: u32,
+        kind: ProficiencyKind,
+    },
+
+    /// Spells picked by the player.
+    SpellChoice {
+        count: u32,
+    },
+
+    Tool(&'static str),
+    ArmorTraining(ArmorCategory),
+    ShieldTraining,
+    WeaponTraining(WeaponCategory),
+
+    /// Add proficiency bonus to initiative rolls.
+    InitiativeProficiency,
+
+    HitPoints(u32),
+    HitPointsPerLevel(u32),
+    Speed(u32),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Feat {
+    pub name: &'static str,
+    pub category: FeatCategory,
+    pub prerequisites: &'static [FeatPrerequisite],
+    pub effects: &'static [FeatEffect],
+    pub repeatable: bool,
+}
+
+impl Feat {
+    pub fn find(name: &str) -> Option<&'static Feat> {
+        FEATS.iter().find(|feat| feat.name == name)
+    }
+
+    pub fn all() -> &'static [Feat] {
+        FEATS
+    }
+
+    /// Number of ability picks required by the feat.
+    pub fn ability_picks(&self) -> u32 {
+        self.effects
+            .iter()
+            .map(|effect| match effect {
+                FeatEffect::AbilityIncrease { count, .. } => *count,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Abilities allowed for picks, with the score limit.
+    pub fn ability_options(&self) -> Option<(&'static [Ability], u32)> {
+        self.effects.iter().find_map(|effect| match *effect {
+            FeatEffect::AbilityIncrease { abilities, max, .. } => Some((abilities, max)),
+            _ => None,
+        })
+    }
+
+    /// Number of proficiency picks required by the feat, with the allowed kind.
+    pub fn proficiency_picks(&self) -> Option<(u32, ProficiencyKind)> {
+        self.effects.iter().find_map(|effect| match *effect {
+            FeatEffect::ProficiencyChoice { count, kind } => Some((count, kind)),
+            _ => None,
+        })
+    }
+
+    pub fn spell_picks(&self) -> u32 {
+        self.effects
+            .iter()
+            .map(|effect| match effect {
+                FeatEffect::SpellChoice { count } => *count,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum FeatChoice {
+    Ability(Ability),
+    Proficiency(Proficiency),
+    Spell(String),
+}
+
+/// A feat taken by the character together with the sub-choices it requires.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct ChosenFeat {
+    pub name: String,
+
+    #[serde(default)]
+    pub choices: Vec<FeatChoice>,
+}
+
+impl ChosenFeat {
+    pub fn new(name: impl Into<String>) -> Self {
+        ChosenFeat {
+            name: name.into(),
+            choices: Vec::new(),
+        }
+    }
+
+    pub fn feat(&self) -> Option<&'static Feat> {
+        Feat::find(&self.name)
+    }
+
+    pub fn abilities(&self) -> impl Iterator<Item = Ability> + '_ {
+        self.choices.iter().filter_map(|choice| match choice {
+            FeatChoice::Ability(ability) => Some(*ability),
+            _ => None,
+        })
+    }
+
+    pub fn proficiencies(&self) -> impl Iterator<Item = &Proficiency> + '_ {
+        self.choices.iter().filter_map(|choice| match choice {
+            FeatChoice::Proficiency(proficiency) => Some(proficiency),
+            _ => None,
+        })
+    }
+
+    pub fn spells(&self) -> impl Iterator<Item = &str> + '_ {
+        self.choices.iter().filter_map(|choice| match choice {
+            FeatChoice::Spell(spell) => Some(spell.as_str()),
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for ChosenFeat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        for (index, choice) in self.choices.iter().enumerate() {
+            f.write_str(if index == 0 { " (" } else { ", " })?;
+            match choice {
+                FeatChoice::Ability(ability) => write!(f, "+1 {ability:?}")?,
+                FeatChoice::Proficiency(proficiency) => write!(f, "{proficiency}")?,
+                FeatChoice::Spell(spell) => f.write_str(spell)?,
+            }
+        }
+        if !self.choices.is_empty() {
+            f.write_str(")")?;
+        }
+        Ok(())
+    }
+}
+
+/// How the character gained a feat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FeatSource {
+    /// Chosen when reaching the total character level.
+    Level(u32),
+
+    /// Origin feat of the background.
+    Background,
+
+    /// Granted outside of level-ups.
+    Granted,
+}
+
+impl fmt::Display for FeatSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatSource::Level(level) => write!(f, "Level {level}"),
+            FeatSource::Background => f.write_str("Background"),
+            FeatSource::Granted => f.write_str("Granted"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FeatError {
+    UnknownFeat(String),
+    WrongCategory(FeatCategory),
+    Prerequisite(FeatPrerequisite),
+    AlreadyTaken,
+    AbilityPicks { expected: u32 },
+    ProficiencyPicks { expected: u32 },
+    SpellPicks { expected: u32 },
+    AbilityNotAllowed(Ability),
+    AbilityScoreAboveMaximum(Ability),
+}
+
+impl fmt::Display for FeatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FeatError::UnknownFeat(name) => write!(f, "Unknown feat: {name}"),
+            FeatError::WrongCategory(category) => {
+                write!(f, "{category:?} feats cannot be chosen here")
+            }
+            FeatError::Prerequisite(FeatPrerequisite::Level(level)) => {
+                write!(f, "Requires level {level}")
+            }
+            FeatError::Prerequisite(FeatPrerequisite::Ability(abilities)) => {
+                write!(f, "Requires {FEAT_MIN_SCORE} in one of {abilities:?}")
+            }
+            FeatError::Prerequisite(FeatPrerequisite::ClassFeature(feature)) => {
+                write!(f, "Requires the {feature} feature")
+            }
+            FeatError::Prerequisite(FeatPrerequisite::Spellcasting) => {
+                write!(f, "Requires the Spellcasting or Pact Magic feature")
+            }
+            FeatError::Prerequisite(FeatPrerequisite::ArmorTraining(category)) => {
+                write!(f, "Requires {category:?} armor training")
+            }
+            FeatError::AlreadyTaken => write!(f, "Feat is already taken and is not repeatable"),
+            FeatError::AbilityPicks { expected } => {
+                write!(f, "Choose {expected} ability increases")
+            }
+            FeatError::ProficiencyPicks { expected } => {
+                write!(f, "Choose {expected} proficiencies")
+            }
+            FeatError::SpellPicks { expected } => write!(f, "Choose {expected} spells"),
+            FeatError::AbilityNotAllowed(ability) => {
+                write!(f, "{ability:?} cannot be increased by this feat")
+            }
+            FeatError::AbilityScoreAboveMaximum(ability) => {
+                write!(f, "{ability:?} cannot be increased further by this feat")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FeatError {}
+
+impl Character {
+    /// All feats of the character, with the way each one was gained.
+    pub fn feats(&self) -> impl Iterator<Item = (FeatSource, &ChosenFeat)> + '_ {
+        let from_levels = self.levels.iter().enumerate().flat_map(|(index, level)| {
+            level
+                .feats()
+                .map(move |feat| (FeatSource::Level(index as u32 + 1), feat))
+        });
+        let background = self
+            .background
+            .iter()
+            .map(|background| (FeatSource::Background, &background.feat));
+        let granted = self.feats.iter().map(|feat| (FeatSource::Granted, feat));
+
+        background.chain(from_levels).chain(granted)
+    }
+
+    /// Returns true if any class level of the character grants the feature.
+    pub fn has_class_feature(&self, feature: &str) -> bool {
+        self.classes()
+            .any(|(class, level)| (1..=level).any(|level| class.features(level).contains(&feature)))
+    }
+
+    pub fn has_spellcasting(&self) -> bool {
+        self.classes()
+            .any(|(class, level)| class.learns_spells(level))
+    }
+
+    pub fn has_armor_training(&self, category: ArmorCategory) -> bool {
+        self.proficiencies()
+            .iter()
+            .any(|(proficiency, _)| *proficiency == Proficiency::Armor(category))
+    }
+
+    /// Checks that the character can take the feat at the given total level.
+    pub fn check_feat(&self, chosen: &ChosenFeat, level: u32) -> Result<&'static Feat, FeatError> {
+        let feat = chosen
+            .feat()
+            .ok_or_else(|| FeatError::UnknownFeat(chosen.name.clone()))?;
+
+        for &prerequisite in feat.prerequisites {
+            let met = match prerequisite {
+                FeatPrerequisite::Level(min) => level >= min,
+                FeatPrerequisite::Ability(abilities) => abilities
+                    .iter()
+                    .any(|&ability| self.ability_score(ability) >= FEAT_MIN_SCORE),
+                FeatPrerequisite::ClassFeature(feature) => self.has_class_feature(feature),
+                FeatPrerequisite::Spellcasting => self.has_spellcasting(),
+                FeatPrerequisite::ArmorTraining(category) => self.has_armor_training(category),
+            };
+            if !met {
+                return Err(FeatError::Prerequisite(prerequisite));
+            }
+        }
+
+        if !feat.repeatable && self.feats().any(|(_, taken)| taken.name == feat.name) {
+            return Err(FeatError::AlreadyTaken);
+        }
+
+        let picks = chosen.abilities().count() as u32;
+        if picks != feat.ability_picks() {
+            return Err(FeatError::AbilityPicks {
+                expected: feat.ability_picks(),
+            });
+        }
+        if let Some((abilities, max)) = feat.ability_options() {
+            for ability in chosen.abilities() {
+                let increase = chosen.abilities().filter(|&a| a == ability).count() as u32;
+                if !abilities.contains(&ability) {
+                    return Err(FeatError::AbilityNotAllowed(ability));
+                }
+                if self.ability_score(ability) + increase > max {
+                    return Err(FeatError::AbilityScoreAboveMaximum(ability));
+                }
+            }
+        }
+
+        let (expected, kind) = feat
+            .proficiency_picks()
+            .unwrap_or((0, ProficiencyKind::SkillOrTool));
+        let proficiencies = chosen.proficiencies().collect::<Vec<_>>();
+        if proficiencies.len() as u32 != expected
+            || !proficiencies
+                .iter()
+                .all(|proficiency| kind.allows(proficiency))
+        {
+            return Err(FeatError::ProficiencyPicks { expected });
+        }
+
+        if chosen.spells().count() as u32 != feat.spell_picks() {
+            return Err(FeatError::SpellPicks {
+                expected: feat.spell_picks(),
+            });
+        }
+
+        Ok(feat)
+    }
+
+    /// Adds a feat granted outside of level-ups.
+    pub fn grant_feat(&mut self, feat: ChosenFeat) -> Result<(), FeatError> {
+        self.check_feat(&feat, self.total_level())?;
+        self.feats.push(feat);
+        Ok(())
+    }
+
+    pub fn remove_granted_feat(&mut self, index: usize) -> Option<ChosenFeat> {
+        (index < self.feats.len()).then(|| self.feats.remove(index))
+    }
+}
+
+const ALL: &[Ability] = &Ability::ALL;
+const STR_DEX: &[Ability] = &[Ability::Strength, Ability::Dexterity];
+const STR_CON: &[Ability] = &[Ability::Strength, Ability::Constitution];
+const DEX_CON: &[Ability] = &[Ability::Dexterity, Ability::Constitution];
+const CON_WIS: &[Ability] = &[Ability::Constitution, Ability::Wisdom];
+const INT_WIS: &[Ability] = &[Ability::Intelligence, Ability::Wisdom];
+const WIS_CHA: &[Ability] = &[Ability::Wisdom, Ability::Charisma];
+const MENTAL: &[Ability] = &[Ability::Intelligence, Ability::Wisdom, Ability::Charisma];
+
+const GENERAL: FeatPrerequisite = FeatPrerequisite::Level(4);
+const EPIC: FeatPrerequisite = FeatPrerequisite::Level(19);
+const FIGHTING_STYLE: FeatPrerequisite = FeatPrerequisite::ClassFeature("Fighting Style");
+
+const fn increase(abilities: &'static [Ability]) -> FeatEffect {
+    FeatEffect::AbilityIncrease {
+        count: 1,
+        abilities,
+        max: MAX_ABILITY_SCORE,
+    }
+}
+
+const fn boon(abilities: &'static [Ability]) -> FeatEffect {
+    FeatEffect::AbilityIncrease {
+        count: 1,
+        abilities,
+        max: 30,
+    }
+}
+
+const fn origin(name: &'static str, effects: &'static [FeatEffect]) -> Feat {
+    Feat {
+        name,
+        category: FeatCategory::Origin,
+        prerequisites: &[],
+        effects,
+        repeatable: false,
+    }
+}
+
+const fn general(
+    name: &'static str,
+    prerequisites: &'static [FeatPrerequisite],
+    effects: &'static [FeatEffect],
+) -> Feat {
+    Feat {
+        name,
+        category: FeatCategory::General,
+        prerequisites,
+        effects,
+        repeatable: false,
+    }
+}
+
+const fn fighting_style(name: &'static str) -> Feat {
+    Feat {
+        name,
+        category: FeatCategory::FightingStyle,
+        prerequisites: &[FIGHTING_STYLE],
+        effects: &[],
+        repeatable: false,
+    }
+}
+
+const fn epic_boon(
+    name: &'static str,
+    prerequisites: &'static [FeatPrerequisite],
+    effects: &'static [FeatEffect],
+) -> Feat {
+    Feat {
+        name,
+        category: FeatCategory::EpicBoon,
+        prerequisites,
+        effects,
+        repeatable: false,
+    }
+}
+
+/// Name of the general feat that stands for a plain Ability Score Improvement.
+pub const ABILITY_SCORE_IMPROVEMENT: &str = "Ability Score Improvement";
+
+static FEATS: &[Feat] = &[
+    // Origin feats.
+    origin("Alert", &[FeatEffect::InitiativeProficiency]),
+    origin(
+        "Crafter",
+        &[FeatEffect::ProficiencyChoice {
+            count: 3,
+            kind: ProficiencyKind::Tool,
+        }],
+    ),
+    origin("Healer", &[]),
+    origin("Lucky", &[]),
+    Feat {
+        repeatable: true,
+        ..origin("Magic Initiate", &[FeatEffect::SpellChoice { count: 3 }])
+    },
+    origin(
+        "Musician",
+        &[FeatEffect::ProficiencyChoice {
+            count: 3,
+            kind: ProficiencyKind::Tool,
+        }],
+    ),
+    origin("Savage Attacker", &[]),
+    Feat {
+        repeatable: true,
+        ..origin(
+            "Skilled",
+            &[FeatEffect::ProficiencyChoice {
+                count: 3,
+                kind: ProficiencyKind::SkillOrTool,
+            }],
+        )
+    },
+    origin("Tavern Brawler", &[]),
+    origin("Tough", &[FeatEffect::HitPointsPerLevel(2)]),
+    // General feats.
+    Feat {
+        repeatable: true,
+        ..general(
+            ABILITY_SCORE_IMPROVEMENT,
+            &[GENERAL],
+            &[FeatEffect::AbilityIncrease {
+                count: 2,
+                abilities: ALL,
+                max: MAX_ABILITY_SCORE,
+            }],
+        )
+    },
+    general(
+        "Actor",
+        &[GENERAL, FeatPrerequisite::Ability(&[Ability::Charisma])],
+        &[increase(&[Ability::Charisma])],
+    ),
+    general(
+        "Athlete",
+        &[GENERAL, FeatPrerequisite::Ability(STR_DEX)],
+        &[increase(STR_DEX)],
+    ),
+    general(
+        "Charger",
+        &[GENERAL, FeatPrerequisite::Ability(STR_DEX)],
+        &[increase(STR_DEX)],
+    ),
+    general(
+        "Chef",
+        &[GENERAL],
+        &[increase(CON_WIS), FeatEffect::Tool("Cook's Utensils")],
+    ),
+    general(
+        "Crusher",
+        &[GENERAL, FeatPrerequisite::Ability(STR_CON)],
+        &[increase(STR_CON)],
+    ),
+    general("Durable", &[GENERAL], &[increase(&[Ability::Constitution])]),
+    general(
+        "Fey Touched",
+        &[GENERAL],
+        &[increase(MENTAL), FeatEffect::SpellChoice { count: 1 }],
+    ),
+    general(
+        "Great Weapon Master",
+        &[GENERAL, FeatPrerequisite::Ability(&[Ability::Strength])],
+        &[increase(&[Ability::Strength])],
+    ),
+    general(
+        "Heavily Armored",
+        &[
+            GENERAL,
+            FeatPrerequisite::ArmorTraining(ArmorCategory::Medium),
+        ],
+        &[
+            increase(&[Ability::Constitution, Ability::Strength]),
+            FeatEffect::ArmorTraining(ArmorCategory::Heavy),
+        ],
+    ),
+    general(
+        "Inspiring Leader",
+        &[GENERAL, FeatPrerequisite::Ability(WIS_CHA)],
+        &[increase(WIS_CHA)],
+    ),
+    general(
+        "Keen Mind",
+        &[GENERAL, FeatPrerequisite::Ability(&[Ability::Intelligence])],
+        &[
+            increase(&[Ability::Intelligence]),
+            FeatEffect::ProficiencyChoice {
+                count: 1,
+                kind: ProficiencyKind::Skill,
+            },
+        ],
+    ),
+    general(
+        "Lightly Armored",
+        &[GENERAL],
+        &[
+            increase(STR_DEX),
+            FeatEffect::ArmorTraining(ArmorCategory::Light),
+            FeatEffect::ShieldTraining,
+        ],
+    ),
+    general(
+        "Martial Weapon Training",
+        &[GENERAL],
+        &[
+            increase(STR_DEX),
+            FeatEffect::WeaponTraining(WeaponCategory::Martial),
+        ],
+    ),
+    general(
+        "Moderately Armored",
+        &[
+            GENERAL,
+            FeatPrerequisite::ArmorTraining(ArmorCategory::Light),
+        ],
+        &[
+            increase(STR_DEX),
+            FeatEffect::ArmorTraining(ArmorCategory::Medium),
+            FeatEffect::ShieldTraining,
+        ],
+    ),
+    general(
+        "Observant",
+        &[GENERAL, FeatPrerequisite::Ability(INT_WIS)],
+        &[
+            increase(INT_WIS),
+            FeatEffect::ProficiencyChoice {
+                count: 1,
+                kind: ProficiencyKind::Skill,
+            },
+        ],
+    ),
+    general(
+        "Resilient",
+        &[GENERAL],
+        &[
+            increase(ALL),
+            FeatEffect::ProficiencyChoice {
+                count: 1,
+                kind: ProficiencyKind::SavingThrow,
+            },
+        ],
+    ),
+    general(
+        "Sentinel",
+        &[GENERAL, FeatPrerequisite::Ability(STR_DEX)],
+        &[increase(STR_DEX)],
+    ),
+    general(
+        "Sharpshooter",
+        &[GENERAL, FeatPrerequisite::Ability(&[Ability::Dexterity])],
+        &[increase(&[Ability::Dexterity])],
+    ),
+    general(
+        "Skill Expert",
+        &[GENERAL],
+        &[
+            increase(ALL),
+            FeatEffect::ProficiencyChoice {
+                count: 1,
+                kind: ProficiencyKind::Skill,
+            },
+        ],
+    ),
+    general(
+        "Speedy",
+        &[GENERAL, FeatPrerequisite::Ability(DEX_CON)],
+        &[increase(DEX_CON), FeatEffect::Speed(10)],
+    ),
+    general(
+        "War Caster",
+        &[GENERAL, FeatPrerequisite::Spellcasting],
+        &[increase(MENTAL)],
+    ),
+    // Fighting style feats.
+    fighting_style("Archery"),
+    fighting_style("Blind Fighting"),
+    fighting_style("Defense"),
+    fighting_style("Dueling"),
+    fighting_style("Great Weapon Fighting"),
+    fighting_style("Interception"),
+    fighting_style("Protection"),
+    fighting_style("Thrown Weapon Fighting"),
+    fighting_style("Two-Weapon Fighting"),
+    fighting_style("Unarmed Fighting"),
+    // Epic boons.
+    epic_boon("Boon of Combat Prowess", &[EPIC], &[boon(ALL)]),
+    epic_boon("Boon of Dimensional Travel", &[EPIC], &[boon(ALL)]),
+    epic_boon("Boon of Energy Resistance", &[EPIC], &[boon(ALL)]),
+    epic_boon("Boon of Fate", &[EPIC], &[boon(ALL)]),
+    epic_boon(
+        "Boon of Fortitude",
+        &[EPIC],
+        &[boon(ALL), FeatEffect::HitPoints(40)],
+    ),
+    epic_boon("Boon of Irresistible Offense", &[EPIC], &[boon(STR_DEX)]),
+    epic_boon("Boon of Recovery", &[EPIC], &[boon(ALL)]),
+    epic_boon("Boon of Skill", &[EPIC], &[boon(ALL)]),
+    epic_boon(
+        "Boon of Speed",
+        &[EPIC],
+        &[boon(ALL), FeatEffect::Speed(30)],
+    ),
+    epic_boon(
+        "Boon of Spell Recall",
+        &[EPIC, FeatPrerequisite::Spellcasting],
+        &[boon(MENTAL)],
+    ),
+    epic_boon("Boon of the Night Spirit", &[EPIC], &[boon(ALL)]),
+    epic_boon("Boon of Truesight", &[EPIC], &[boon(ALL)]),
+];
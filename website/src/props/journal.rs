@@ -0,0 +1,350 @@
+use std::fmt;
+
+use serde_json::Value;
+
+use super::{
+    diff::{diff_values, ArrayDiff},
+    Character, SchemaError,
+};
+
+/// Most edits kept in a journal; the oldest ones are dropped first.
+pub const MAX_JOURNAL_EDITS: usize = 200;
+
+/// Milliseconds within which changes of the same fields are merged into one edit,
+/// so that typing a name is a single step of the history.
+pub const JOURNAL_MERGE_WINDOW: u64 = 2000;
+
+/// Change of one field of the character document.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct FieldChange {
+    /// Path of the field, such as `str`, `classes.Fighter` or `spell_slots_spent[0]`.
+    pub path: String,
+
+    /// Value before the change, absent when the field was added.
+    pub old: Option<Value>,
+
+    /// Value after the change, absent when the field was removed.
+    pub new: Option<Value>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = |value: &Option<Value>| match value {
+            None => "none".to_owned(),
+            Some(value) => {
+                let text = value.to_string();
+                match text.char_indices().nth(40) {
+                    Some((end, _)) => format!("{}…", &text[..end]),
+                    None => text,
+                }
+            }
+        };
+        write!(
+            f,
+            "{}: {} → {}",
+            self.path,
+            value(&self.old),
+            value(&self.new)
+        )
+    }
+}
+
+/// Changes made together, such as all the changes of a long rest.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Edit {
+    /// Milliseconds since the Unix epoch.
+    pub timestamp: u64,
+    pub changes: Vec<FieldChange>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum JournalError {
+    /// No state of the history has this number of applied edits.
+    InvalidPosition(usize),
+
+    /// The journal does not match the character document.
+    MissingField(String),
+    Schema(SchemaError),
+}
+
+impl fmt::Display for JournalError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JournalError::InvalidPosition(position) => {
+                write!(f, "No state {position} in the history")
+            }
+            JournalError::MissingField(path) => {
+                write!(
+                    f,
+                    "Field {path} of the history is missing from the character"
+                )
+            }
+            JournalError::Schema(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl std::error::Error for JournalError {}
+
+impl From<SchemaError> for JournalError {
+    fn from(err: SchemaError) -> Self {
+        JournalError::Schema(err)
+    }
+}
+
+/// History of edits of a character, with undo and redo.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Journal {
+    edits: Vec<Edit>,
+
+    /// Number of edits in effect; the ones after it were undone and can be redone.
+    applied: usize,
+}
+
+impl Journal {
+    /// Edits, oldest first.
+    pub fn edits(&self) -> &[Edit] {
+        &self.edits
+    }
+
+    pub fn applied(&self) -> usize {
+        self.applied
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.applied > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.applied < self.edits.len()
+    }
+
+    /// Records the changes between two documents of the character, dropping the undone edits.
+    ///
+    /// Returns `false` when there is nothing to record. Upgrades of the schema version
+    /// are not recorded, as their changes cannot be undone field by field.
+    pub fn record(&mut self, before: &Value, after: &Value, timestamp: u64) -> bool {
+        if before.get("version") != after.get("version") {
+            return false;
+        }
+
+        let mut changes = Vec::new();
+        diff_values(
+            String::new(),
+            Some(before),
+            Some(after),
+            ArrayDiff::Whole,
+            &mut changes,
+        );
+        if changes.is_empty() {
+            return false;
+        }
+
+        self.edits.truncate(self.applied);
+        match self.edits.last_mut() {
+            Some(last)
+                if timestamp.saturating_sub(last.timestamp) < JOURNAL_MERGE_WINDOW
+                    && same_fields(&last.changes, &changes) =>
+            {
+                for (change, merged) in last.changes.iter_mut().zip(changes) {
+                    change.new = merged.new;
+                }
+                last.timestamp = timestamp;
+                last.changes.retain(|change| change.old != change.new);
+                if last.changes.is_empty() {
+                    self.edits.pop();
+                }
+            }
+            _ => {
+                self.edits.push(Edit { timestamp, changes });
+                if self.edits.len() > MAX_JOURNAL_EDITS {
+                    self.edits.remove(0);
+                }
+            }
+        }
+        self.applied = self.edits.len();
+        true
+    }
+
+    pub fn undo(&mut self, character: &Character) -> Result<Character, JournalError> {
+        let position = self
+            .applied
+            .checked_sub(1)
+            .ok_or(JournalError::InvalidPosition(0))?;
+        self.restore(position, character)
+    }
+
+    pub fn redo(&mut self, character: &Character) -> Result<Character, JournalError> {
+        self.restore(self.applied + 1, character)
+    }
+
+    /// Brings the character to the state after the first `applied` edits.
+    ///
+    /// Nothing is changed if the journal does not match the character.
+    pub fn restore(
+        &mut self,
+        applied: usize,
+        character: &Character,
+    ) -> Result<Character, JournalError> {
+        if applied > self.edits.len() {
+            return Err(JournalError::InvalidPosition(applied));
+        }
+
+        let mut document = character.to_json_value();
+        let undone = self.edits.get(applied..self.applied).unwrap_or_default();
+        for edit in undone.iter().rev() {
+            for change in edit.changes.iter().rev() {
+                set_field(&mut document, &change.path, change.old.clone())?;
+            }
+        }
+        for edit in self.edits.get(self.applied..applied).unwrap_or_default() {
+            for change in &edit.changes {
+                set_field(&mut document, &change.path, change.new.clone())?;
+            }
+        }
+
+        let character = Character::from_json_value(document)?;
+        self.applied = applied;
+        Ok(character)
+    }
+}
+
+fn same_fields(a: &[FieldChange], b: &[FieldChange]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.path == b.path)
+}
+
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+fn segments(path: &str) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    for part in path.split('.') {
+        let mut pieces = part.split('[');
+        segments.push(Segment::Key(pieces.next()?));
+        for index in pieces {
+            segments.push(Segment::Index(index.strip_suffix(']')?.parse().ok()?));
+        }
+    }
+    Some(segments)
+}
+
+/// Sets the field at the path, removing it from its object when `value` is absent.
+fn set_field(document: &mut Value, path: &str, value: Option<Value>) -> Result<(), JournalError> {
+    let missing = || JournalError::MissingField(path.to_owned());
+    let segments = segments(path).ok_or_else(missing)?;
+    let (last, parents) = segments.split_last().ok_or_else(missing)?;
+
+    let mut target = document;
+    for segment in parents {
+        target = match segment {
+            Segment::Key(key) => target.get_mut(*key),
+            Segment::Index(index) => target.get_mut(*index),
+        }
+        .ok_or_else(missing)?;
+    }
+
+    match (last, target, value) {
+        (Segment::Key(key), Value::Object(object), Some(value)) => {
+            object.insert((*key).to_owned(), value);
+        }
+        (Segment::Key(key), Value::Object(object), None) => {
+            object.remove(*key);
+        }
+        (Segment::Index(index), Value::Array(array), Some(value)) if *index < array.len() => {
+            array[*index] = value;
+        }
+        _ => return Err(missing()),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::props::Ability;
+
+    fn edited(character: &Character, edit: impl FnOnce(&mut Character)) -> Character {
+        let mut edited = character.clone();
+        edit(&mut edited);
+        edited
+    }
+
+    fn record(journal: &mut Journal, before: &Character, after: &Character, timestamp: u64) {
+        assert!(journal.record(&before.to_json_value(), &after.to_json_value(), timestamp));
+    }
+
+    #[test]
+    fn undo_and_redo_restore_fields() {
+        let start = Character::new();
+        let stronger = edited(&start, |c| {
+            c.set_base_ability_score(Ability::Strength, 14).unwrap()
+        });
+        let hurt = edited(&stronger, |c| c.damage = 3);
+
+        let mut journal = Journal::default();
+        record(&mut journal, &start, &stronger, 0);
+        record(&mut journal, &stronger, &hurt, 10_000);
+        assert_eq!(journal.edits()[1].changes[0].path, "damage");
+
+        let undone = journal.undo(&hurt).unwrap();
+        assert_eq!(undone, stronger);
+        assert_eq!(journal.restore(0, &undone).unwrap(), start);
+        assert!(!journal.can_undo());
+        assert_eq!(journal.redo(&start).unwrap(), stronger);
+        assert_eq!(journal.restore(2, &stronger).unwrap(), hurt);
+        assert!(!journal.can_redo());
+    }
+
+    #[test]
+    fn added_and_removed_fields_are_restored() {
+        let start = Character::new();
+        let mut leveled = start.clone();
+        leveled.classes.insert(crate::props::Class::Fighter, 1);
+
+        let mut journal = Journal::default();
+        record(&mut journal, &start, &leveled, 0);
+        assert_eq!(journal.edits()[0].changes[0].path, "classes.Fighter");
+        assert_eq!(journal.edits()[0].changes[0].old, None);
+
+        assert_eq!(journal.undo(&leveled).unwrap(), start);
+        assert_eq!(journal.redo(&start).unwrap(), leveled);
+    }
+
+    #[test]
+    fn quick_edits_of_a_field_are_merged() {
+        let start = Character::new();
+        let typed = edited(&start, |c| c.name = "T".to_owned());
+        let name = edited(&typed, |c| c.name = "Tordek".to_owned());
+
+        let mut journal = Journal::default();
+        record(&mut journal, &start, &typed, 0);
+        record(&mut journal, &typed, &name, JOURNAL_MERGE_WINDOW - 1);
+        assert_eq!(journal.edits().len(), 1);
+        assert_eq!(journal.undo(&name).unwrap(), start);
+
+        // A new edit replaces the undone ones.
+        let other = edited(&start, |c| c.origin = "Mountains".to_owned());
+        record(&mut journal, &start, &other, 2 * JOURNAL_MERGE_WINDOW);
+        assert_eq!(journal.edits().len(), 1);
+        assert!(!journal.can_redo());
+    }
+
+    #[test]
+    fn mismatched_journal_changes_nothing() {
+        let start = Character::new();
+        let mut leveled = start.clone();
+        leveled.classes.insert(crate::props::Class::Fighter, 1);
+
+        let mut journal = Journal::default();
+        record(&mut journal, &start, &leveled, 0);
+        journal.edits[0].changes[0].path = "levels[5].class".to_owned();
+
+        assert!(matches!(
+            journal.undo(&leveled),
+            Err(JournalError::MissingField(_))
+        ));
+        assert_eq!(journal.applied(), 1);
+    }
+}
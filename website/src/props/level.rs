@@ -0,0 +1,283 @@
+use std::fmt;
+
+use super::{Character, ChosenFeat, Class, FeatCategory, FeatError};
+
+/// Highest total character level.
+pub const MAX_LEVEL: u32 = 20;
+
+/// How hit points are gained for a new level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum HitPointsGain {
+    /// Take the fixed value of the hit die, half of its sides plus one.
+    Average,
+
+    /// Use the result of a hit die roll.
+    Rolled(u32),
+}
+
+/// A choice the player has to make when gaining a class level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LevelUpPrompt {
+    /// Ability Score Improvement feat or any other feat the character qualifies for.
+    AbilityScoreImprovementOrFeat,
+    FightingStyle,
+    Subclass,
+    Spells,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LevelUpChoice {
+    Feat(ChosenFeat),
+    FightingStyle(ChosenFeat),
+    Subclass(String),
+    Spells(Vec<String>),
+}
+
+impl LevelUpChoice {
+    /// Prompt this choice answers.
+    pub fn prompt(&self) -> LevelUpPrompt {
+        match self {
+            LevelUpChoice::Feat(_) => LevelUpPrompt::AbilityScoreImprovementOrFeat,
+            LevelUpChoice::FightingStyle(_) => LevelUpPrompt::FightingStyle,
+            LevelUpChoice::Subclass(_) => LevelUpPrompt::Subclass,
+            LevelUpChoice::Spells(_) => LevelUpPrompt::Spells,
+        }
+    }
+}
+
+impl fmt::Display for LevelUpChoice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelUpChoice::Feat(feat) | LevelUpChoice::FightingStyle(feat) => feat.fmt(f),
+            LevelUpChoice::Subclass(subclass) => f.write_str(subclass),
+            LevelUpChoice::Spells(spells) => f.write_str(&spells.join(", ")),
+        }
+    }
+}
+
+/// Everything needed to gain one level.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelUp {
+    pub class: Class,
+    pub hit_points: HitPointsGain,
+    pub choices: Vec<LevelUpChoice>,
+}
+
+/// A level gained by the character, as recorded in its history.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LevelRecord {
+    pub class: Class,
+    pub class_level: u32,
+
+    /// Hit points gained from the hit die, without the Constitution modifier.
+    pub hit_points: u32,
+
+    #[serde(default)]
+    pub choices: Vec<LevelUpChoice>,
+}
+
+impl LevelRecord {
+    /// Feats chosen at this level.
+    pub fn feats(&self) -> impl Iterator<Item = &ChosenFeat> + '_ {
+        self.choices.iter().filter_map(|choice| match choice {
+            LevelUpChoice::Feat(feat) | LevelUpChoice::FightingStyle(feat) => Some(feat),
+            _ => None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LevelUpError {
+    MaxLevel,
+    MulticlassPrerequisite(Class),
+    InvalidHitPointRoll(u32),
+    MissingChoice(LevelUpPrompt),
+    UnexpectedChoice(LevelUpPrompt),
+    Feat(FeatError),
+}
+
+impl fmt::Display for LevelUpError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelUpError::MaxLevel => write!(f, "Character is already level {MAX_LEVEL}"),
+            LevelUpError::MulticlassPrerequisite(class) => {
+                write!(f, "Multiclass prerequisites of {class:?} are not met")
+            }
+            LevelUpError::InvalidHitPointRoll(roll) => {
+                write!(
+                    f,
+                    "Hit point roll {roll} is not possible with the class hit die"
+                )
+            }
+            LevelUpError::MissingChoice(prompt) => write!(f, "Missing choice: {prompt:?}"),
+            LevelUpError::UnexpectedChoice(prompt) => {
+                write!(f, "Choice is not available at this level: {prompt:?}")
+            }
+            LevelUpError::Feat(err) => err.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for LevelUpError {}
+
+impl From<FeatError> for LevelUpError {
+    fn from(err: FeatError) -> Self {
+        LevelUpError::Feat(err)
+    }
+}
+
+impl Character {
+    /// Levels gained by the character, in order.
+    pub fn levels(&self) -> &[LevelRecord] {
+        &self.levels
+    }
+
+    /// Subclass chosen for the class, if any.
+    pub fn subclass(&self, class: Class) -> Option<&str> {
+        self.levels
+            .iter()
+            .filter(|level| level.class == class)
+            .flat_map(|level| &level.choices)
+            .find_map(|choice| match choice {
+                LevelUpChoice::Subclass(name) => Some(name.as_str()),
+                _ => None,
+            })
+    }
+
+    /// Checks multiclass prerequisites for taking a level in the class.
+    ///
+    /// A character that has no levels yet, or already has levels in the class, is not multiclassing.
+    /// Otherwise prerequisites of both the new class and every current class must be met.
+    pub fn check_multiclass(&self, class: Class) -> Result<(), LevelUpError> {
+        if self.total_level() == 0 || self.class_level(class) > 0 {
+            return Ok(());
+        }
+
+        let score = |ability| self.ability_score(ability);
+
+        std::iter::once(class)
+            .chain(self.classes().map(|(class, _)| class))
+            .try_for_each(|class| {
+                if class.multiclass_prerequisite().is_met(score) {
+                    Ok(())
+                } else {
+                    Err(LevelUpError::MulticlassPrerequisite(class))
+                }
+            })
+    }
+
+    /// Choices due when taking the next level in the class.
+    pub fn level_up_prompts(&self, class: Class) -> Vec<LevelUpPrompt> {
+        let level = self.class_level(class) + 1;
+
+        let mut prompts = Vec::new();
+        if class.grants_ability_score_improvement(level) {
+            prompts.push(LevelUpPrompt::AbilityScoreImprovementOrFeat);
+        }
+        if class.features(level).contains(&"Fighting Style") {
+            prompts.push(LevelUpPrompt::FightingStyle);
+        }
+        if level == class.subclass_level() {
+            prompts.push(LevelUpPrompt::Subclass);
+        }
+        if class.learns_spells(level) {
+            prompts.push(LevelUpPrompt::Spells);
+        }
+        prompts
+    }
+
+    /// Gains one level as a single transaction.
+    ///
+    /// Nothing is changed if any check fails.
+    pub fn level_up(&mut self, level_up: LevelUp) -> Result<&LevelRecord, LevelUpError> {
+        let LevelUp {
+            class,
+            hit_points,
+            choices,
+        } = level_up;
+
+        if self.total_level() >= MAX_LEVEL {
+            return Err(LevelUpError::MaxLevel);
+        }
+
+        self.check_multiclass(class)?;
+
+        let prompts = self.level_up_prompts(class);
+        for (index, choice) in choices.iter().enumerate() {
+            let prompt = choice.prompt();
+            if !prompts.contains(&prompt)
+                || choices[..index]
+                    .iter()
+                    .any(|other| other.prompt() == prompt)
+            {
+                return Err(LevelUpError::UnexpectedChoice(prompt));
+            }
+        }
+        for &prompt in &prompts {
+            // Spells may be left for later, every other choice is mandatory.
+            if prompt != LevelUpPrompt::Spells
+                && !choices.iter().any(|choice| choice.prompt() == prompt)
+            {
+                return Err(LevelUpError::MissingChoice(prompt));
+            }
+        }
+
+        let die = class.hit_die();
+        let hit_points = if self.total_level() == 0 {
+            // First character level always takes the maximum of the hit die.
+            die.sides()
+        } else {
+            match hit_points {
+                HitPointsGain::Average => die.sides() / 2 + 1,
+                HitPointsGain::Rolled(roll) if (1..=die.sides()).contains(&roll) => roll,
+                HitPointsGain::Rolled(roll) => return Err(LevelUpError::InvalidHitPointRoll(roll)),
+            }
+        };
+
+        // Feats are checked against the character as it would be with the new class level,
+        // so that features gained at this level count as prerequisites.
+        let mut next = self.clone();
+        let class_level = next.class_level(class) + 1;
+        next.classes.insert(class, class_level);
+
+        let level = next.total_level();
+        for choice in &choices {
+            match choice {
+                LevelUpChoice::Feat(feat) => {
+                    next.check_feat(feat, level)?;
+                }
+                LevelUpChoice::FightingStyle(feat) => {
+                    let category = next.check_feat(feat, level)?.category;
+                    if category != FeatCategory::FightingStyle {
+                        return Err(FeatError::WrongCategory(category).into());
+                    }
+                }
+                LevelUpChoice::Subclass(_) | LevelUpChoice::Spells(_) => {}
+            }
+        }
+
+        next.levels.push(LevelRecord {
+            class,
+            class_level,
+            hit_points,
+            choices,
+        });
+        *self = next;
+
+        Ok(self.levels.last().unwrap())
+    }
+
+    /// Reverts the last level-up.
+    pub fn revert_level_up(&mut self) -> Option<LevelRecord> {
+        let level = self.levels.pop()?;
+
+        match self.classes.get_mut(&level.class) {
+            Some(class_level) if *class_level > 1 => *class_level -= 1,
+            _ => {
+                self.classes.remove(&level.class);
+            }
+        }
+
+        Some(level)
+    }
+}
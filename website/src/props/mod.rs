@@ -1,12 +1,45 @@
 
+mod ability;
+mod background;
+mod bonus;
 mod character;
+mod class;
+mod condition;
 mod equipment;
 mod damage;
+mod diff;
+mod feat;
+mod journal;
+mod level;
+mod proficiency;
+mod resource;
+mod rest;
+mod roll;
+mod schema;
+mod sheet;
+mod spell;
+mod validation;
 
 pub use self::{
+    ability::*,
+    background::*,
+    bonus::*,
     character::*,
+    class::*,
+    condition::*,
     equipment::*,
     damage::*,
+    diff::*,
+    feat::*,
+    journal::*,
+    level::*,
+    proficiency::*,
+    resource::*,
+    rest::*,
+    roll::*,
+    schema::*,
+    spell::*,
+    validation::*,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -20,6 +53,38 @@ pub enum Dice {
     D100,
 }
 
+impl Dice {
+    pub fn sides(self) -> u32 {
+        match self {
+            Dice::D4 => 4,
+            Dice::D6 => 6,
+            Dice::D8 => 8,
+            Dice::D10 => 10,
+            Dice::D12 => 12,
+            Dice::D20 => 20,
+            Dice::D100 => 100,
+        }
+    }
+
+    pub fn roll(self) -> u32 {
+        rand::random_range(1..=self.sides())
+    }
+
+    /// Die with the given number of sides, such as `Dice::D6` for 6.
+    pub fn from_sides(sides: u32) -> Option<Self> {
+        match sides {
+            4 => Some(Dice::D4),
+            6 => Some(Dice::D6),
+            8 => Some(Dice::D8),
+            10 => Some(Dice::D10),
+            12 => Some(Dice::D12),
+            20 => Some(Dice::D20),
+            100 => Some(Dice::D100),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub struct RollValue {
     flat: u32,
@@ -30,4 +95,5 @@ pub struct RollValue {
 pub enum RollModifier {
     Advantage,
     Disadvantage,
+    AutomaticFailure,
 }
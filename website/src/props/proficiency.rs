@@ -0,0 +1,150 @@
+use std::{fmt, str::FromStr};
+
+use super::{Ability, ArmorCategory, WeaponCategory};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Skill {
+    Acrobatics,
+    AnimalHandling,
+    Arcana,
+    Athletics,
+    Deception,
+    History,
+    Insight,
+    Intimidation,
+    Investigation,
+    Medicine,
+    Nature,
+    Perception,
+    Performance,
+    Persuasion,
+    Religion,
+    SleightOfHand,
+    Stealth,
+    Survival,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidSkill;
+
+impl FromStr for Skill {
+    type Err = InvalidSkill;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "Acrobatics" => Ok(Skill::Acrobatics),
+            "AnimalHandling" => Ok(Skill::AnimalHandling),
+            "Arcana" => Ok(Skill::Arcana),
+            "Athletics" => Ok(Skill::Athletics),
+            "Deception" => Ok(Skill::Deception),
+            "History" => Ok(Skill::History),
+            "Insight" => Ok(Skill::Insight),
+            "Intimidation" => Ok(Skill::Intimidation),
+            "Investigation" => Ok(Skill::Investigation),
+            "Medicine" => Ok(Skill::Medicine),
+            "Nature" => Ok(Skill::Nature),
+            "Perception" => Ok(Skill::Perception),
+            "Performance" => Ok(Skill::Performance),
+            "Persuasion" => Ok(Skill::Persuasion),
+            "Religion" => Ok(Skill::Religion),
+            "SleightOfHand" => Ok(Skill::SleightOfHand),
+            "Stealth" => Ok(Skill::Stealth),
+            "Survival" => Ok(Skill::Survival),
+            _ => Err(InvalidSkill),
+        }
+    }
+}
+
+impl Skill {
+    pub const ALL: [Skill; 18] = [
+        Skill::Acrobatics,
+        Skill::AnimalHandling,
+        Skill::Arcana,
+        Skill::Athletics,
+        Skill::Deception,
+        Skill::History,
+        Skill::Insight,
+        Skill::Intimidation,
+        Skill::Investigation,
+        Skill::Medicine,
+        Skill::Nature,
+        Skill::Perception,
+        Skill::Performance,
+        Skill::Persuasion,
+        Skill::Religion,
+        Skill::SleightOfHand,
+        Skill::Stealth,
+        Skill::Survival,
+    ];
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Proficiency {
+    Skill(Skill),
+    SavingThrow(Ability),
+    Tool(String),
+    Armor(ArmorCategory),
+    Shields,
+    Weapon(WeaponCategory),
+}
+
+impl fmt::Display for Proficiency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Proficiency::Skill(skill) => write!(f, "{skill:?}"),
+            Proficiency::SavingThrow(ability) => write!(f, "{ability:?} saving throws"),
+            Proficiency::Tool(tool) => f.write_str(tool),
+            Proficiency::Armor(category) => write!(f, "{category:?} armor"),
+            Proficiency::Shields => f.write_str("Shields"),
+            Proficiency::Weapon(category) => write!(f, "{category:?} weapons"),
+        }
+    }
+}
+
+/// Kind of proficiency a player may pick when a choice is offered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProficiencyKind {
+    Skill,
+    Tool,
+    SkillOrTool,
+    SavingThrow,
+}
+
+impl ProficiencyKind {
+    pub fn allows(self, proficiency: &Proficiency) -> bool {
+        matches!(
+            (self, proficiency),
+            (ProficiencyKind::Skill, Proficiency::Skill(_))
+                | (ProficiencyKind::Tool, Proficiency::Tool(_))
+                | (
+                    ProficiencyKind::SkillOrTool,
+                    Proficiency::Skill(_) | Proficiency::Tool(_)
+                )
+                | (ProficiencyKind::SavingThrow, Proficiency::SavingThrow(_))
+        )
+    }
+
+    /// Parses a picked proficiency of this kind.
+    ///
+    /// Skills and abilities are matched by name, anything else is taken as a tool name.
+    pub fn parse(self, s: &str) -> Option<Proficiency> {
+        let s = s.trim();
+        let proficiency = match (self, Skill::from_str(s)) {
+            (ProficiencyKind::SavingThrow, _) => Proficiency::SavingThrow(s.parse().ok()?),
+            (ProficiencyKind::Skill | ProficiencyKind::SkillOrTool, Ok(skill)) => {
+                Proficiency::Skill(skill)
+            }
+            (ProficiencyKind::Skill, Err(_)) => return None,
+            (ProficiencyKind::Tool | ProficiencyKind::SkillOrTool, _) if !s.is_empty() => {
+                Proficiency::Tool(s.to_owned())
+            }
+            _ => return None,
+        };
+        Some(proficiency)
+    }
+}
+
+/// Proficiency bonus for the total character level.
+pub fn proficiency_bonus(level: u32) -> i32 {
+    2 + (level.max(1) as i32 - 1) / 4
+}
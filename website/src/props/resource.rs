@@ -0,0 +1,446 @@
+use std::fmt;
+
+use super::{Ability, Character, Class, MAX_LEVEL};
+
+/// When expended uses of a resource come back.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Recovery {
+    /// All uses on a short or long rest.
+    ShortRest,
+
+    /// One use on a short rest, all uses on a long rest.
+    OneOnShortRest,
+
+    LongRest,
+
+    /// All uses at dawn, regardless of rests.
+    Dawn,
+}
+
+impl Recovery {
+    /// Uses recovered out of `spent` when `event` happens.
+    ///
+    /// `event` is `ShortRest`, `LongRest` or `Dawn`.
+    pub fn recovered(self, event: Recovery, spent: u32) -> u32 {
+        match (self, event) {
+            (Recovery::OneOnShortRest, Recovery::ShortRest) => spent.min(1),
+            (Recovery::ShortRest, Recovery::ShortRest | Recovery::LongRest)
+            | (Recovery::OneOnShortRest | Recovery::LongRest, Recovery::LongRest)
+            | (Recovery::Dawn, Recovery::Dawn) => spent,
+            _ => 0,
+        }
+    }
+}
+
+impl fmt::Display for Recovery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Recovery::ShortRest => "Short rest",
+            Recovery::OneOnShortRest => "One on a short rest, all on a long rest",
+            Recovery::LongRest => "Long rest",
+            Recovery::Dawn => "Dawn",
+        })
+    }
+}
+
+/// How many uses a resource has.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResourceMaximum {
+    Fixed(u32),
+
+    /// Uses by class level.
+    Table(&'static [u32; 20]),
+
+    /// Class level times the multiplier.
+    ClassLevel(u32),
+
+    /// Modifier of the ability, at least 1.
+    AbilityModifier(Ability),
+}
+
+/// Limited-use class feature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Resource {
+    pub name: &'static str,
+    pub class: Class,
+
+    /// Class level the resource is gained at.
+    pub level: u32,
+    pub maximum: ResourceMaximum,
+    pub recovery: Recovery,
+
+    /// Class level from which all uses come back on a short rest.
+    pub short_rest_level: Option<u32>,
+}
+
+impl Resource {
+    pub fn find(class: Class, name: &str) -> Option<&'static Resource> {
+        RESOURCES
+            .iter()
+            .find(|resource| resource.class == class && resource.name == name)
+    }
+
+    pub fn recovery(&self, class_level: u32) -> Recovery {
+        match self.short_rest_level {
+            Some(level) if class_level >= level => Recovery::ShortRest,
+            _ => self.recovery,
+        }
+    }
+}
+
+/// Expended uses of a resource.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct SpentResource {
+    pub class: Class,
+    pub name: String,
+    pub spent: u32,
+}
+
+/// Resource available to the character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResourceState {
+    pub resource: &'static Resource,
+    pub maximum: u32,
+    pub spent: u32,
+    pub recovery: Recovery,
+}
+
+impl ResourceState {
+    pub fn available(&self) -> u32 {
+        self.maximum.saturating_sub(self.spent)
+    }
+}
+
+/// Uses of a resource that came back.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RecoveredResource {
+    pub class: Class,
+    pub name: String,
+    pub recovered: u32,
+}
+
+impl fmt::Display for RecoveredResource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} +{}", self.name, self.recovered)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ResourceError {
+    UnknownResource(String),
+    NotEnoughUses { name: &'static str, available: u32 },
+}
+
+impl fmt::Display for ResourceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResourceError::UnknownResource(name) => write!(f, "Unknown resource: {name}"),
+            ResourceError::NotEnoughUses { name, available } => {
+                write!(f, "{name} has only {available} uses left")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ResourceError {}
+
+impl Character {
+    /// Limited-use features gained from class levels.
+    pub fn resources(&self) -> Vec<ResourceState> {
+        RESOURCES
+            .iter()
+            .filter_map(|resource| self.resource(resource.class, resource.name).ok())
+            .collect()
+    }
+
+    pub fn resource(&self, class: Class, name: &str) -> Result<ResourceState, ResourceError> {
+        let unknown = || ResourceError::UnknownResource(name.to_owned());
+        let resource = Resource::find(class, name).ok_or_else(unknown)?;
+        let class_level = self.class_level(class);
+        if class_level < resource.level {
+            return Err(unknown());
+        }
+
+        let maximum = match resource.maximum {
+            ResourceMaximum::Fixed(uses) => uses,
+            ResourceMaximum::Table(table) => table[class_level.min(MAX_LEVEL) as usize - 1],
+            ResourceMaximum::ClassLevel(multiplier) => class_level * multiplier,
+            ResourceMaximum::AbilityModifier(ability) => {
+                self.ability_modifier(ability).max(1) as u32
+            }
+        };
+        let spent = self
+            .resources_spent
+            .iter()
+            .find(|spent| spent.class == class && spent.name == name)
+            .map_or(0, |spent| spent.spent);
+
+        Ok(ResourceState {
+            resource,
+            maximum,
+            spent,
+            recovery: resource.recovery(class_level),
+        })
+    }
+
+    /// Sets the number of expended uses of a resource.
+    pub fn set_resource_spent(
+        &mut self,
+        class: Class,
+        name: &str,
+        spent: u32,
+    ) -> Result<(), ResourceError> {
+        let state = self.resource(class, name)?;
+        if spent > state.maximum {
+            return Err(ResourceError::NotEnoughUses {
+                name: state.resource.name,
+                available: state.available(),
+            });
+        }
+
+        self.write_resource_spent(class, name, spent);
+        Ok(())
+    }
+
+    pub(super) fn write_resource_spent(&mut self, class: Class, name: &str, spent: u32) {
+        self.resources_spent
+            .retain(|spent| !(spent.class == class && spent.name == name));
+        if spent > 0 {
+            self.resources_spent.push(SpentResource {
+                class,
+                name: name.to_owned(),
+                spent,
+            });
+        }
+    }
+
+    pub fn spend_resource(
+        &mut self,
+        class: Class,
+        name: &str,
+        uses: u32,
+    ) -> Result<(), ResourceError> {
+        let state = self.resource(class, name)?;
+        if uses > state.available() {
+            return Err(ResourceError::NotEnoughUses {
+                name: state.resource.name,
+                available: state.available(),
+            });
+        }
+        self.set_resource_spent(class, name, state.spent + uses)
+    }
+
+    /// Recovers resources on a short rest, long rest or dawn.
+    pub fn recover_resources(&mut self, event: Recovery) -> Vec<RecoveredResource> {
+        let mut recovered = Vec::new();
+        for state in self.resources() {
+            let spent = state.spent.min(state.maximum);
+            let uses = state.recovery.recovered(event, spent);
+            if uses == 0 {
+                continue;
+            }
+            let resource = state.resource;
+            self.write_resource_spent(resource.class, resource.name, spent - uses);
+            recovered.push(RecoveredResource {
+                class: resource.class,
+                name: resource.name.to_owned(),
+                recovered: uses,
+            });
+        }
+        recovered
+    }
+}
+
+static RAGE: [u32; 20] = [2, 2, 3, 3, 3, 4, 4, 4, 4, 4, 4, 5, 5, 5, 5, 5, 6, 6, 6, 6];
+static CLERIC_CHANNEL_DIVINITY: [u32; 20] =
+    [0, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 4, 4, 4];
+static PALADIN_CHANNEL_DIVINITY: [u32; 20] =
+    [0, 0, 2, 2, 2, 2, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3];
+static WILD_SHAPE: [u32; 20] = [0, 2, 2, 2, 2, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4];
+static SECOND_WIND: [u32; 20] = [2, 2, 2, 3, 3, 3, 3, 3, 3, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4, 4];
+static ACTION_SURGE: [u32; 20] = [0, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 1, 2, 2, 2, 2];
+static INDOMITABLE: [u32; 20] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3];
+static FAVORED_ENEMY: [u32; 20] = [2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 6, 6, 6, 6];
+
+const fn resource(
+    class: Class,
+    level: u32,
+    name: &'static str,
+    maximum: ResourceMaximum,
+    recovery: Recovery,
+) -> Resource {
+    Resource {
+        name,
+        class,
+        level,
+        maximum,
+        recovery,
+        short_rest_level: None,
+    }
+}
+
+static RESOURCES: &[Resource] = &[
+    resource(
+        Class::Artificer,
+        7,
+        "Flash of Genius",
+        ResourceMaximum::AbilityModifier(Ability::Intelligence),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Barbarian,
+        1,
+        "Rage",
+        ResourceMaximum::Table(&RAGE),
+        Recovery::OneOnShortRest,
+    ),
+    Resource {
+        short_rest_level: Some(5),
+        ..resource(
+            Class::Bard,
+            1,
+            "Bardic Inspiration",
+            ResourceMaximum::AbilityModifier(Ability::Charisma),
+            Recovery::LongRest,
+        )
+    },
+    resource(
+        Class::Cleric,
+        2,
+        "Channel Divinity",
+        ResourceMaximum::Table(&CLERIC_CHANNEL_DIVINITY),
+        Recovery::OneOnShortRest,
+    ),
+    resource(
+        Class::Cleric,
+        10,
+        "Divine Intervention",
+        ResourceMaximum::Fixed(1),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Druid,
+        2,
+        "Wild Shape",
+        ResourceMaximum::Table(&WILD_SHAPE),
+        Recovery::OneOnShortRest,
+    ),
+    resource(
+        Class::Fighter,
+        1,
+        "Second Wind",
+        ResourceMaximum::Table(&SECOND_WIND),
+        Recovery::OneOnShortRest,
+    ),
+    resource(
+        Class::Fighter,
+        2,
+        "Action Surge",
+        ResourceMaximum::Table(&ACTION_SURGE),
+        Recovery::ShortRest,
+    ),
+    resource(
+        Class::Fighter,
+        9,
+        "Indomitable",
+        ResourceMaximum::Table(&INDOMITABLE),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Monk,
+        2,
+        "Focus Points",
+        ResourceMaximum::ClassLevel(1),
+        Recovery::ShortRest,
+    ),
+    resource(
+        Class::Monk,
+        2,
+        "Uncanny Metabolism",
+        ResourceMaximum::Fixed(1),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Paladin,
+        1,
+        "Lay on Hands",
+        ResourceMaximum::ClassLevel(5),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Paladin,
+        3,
+        "Channel Divinity",
+        ResourceMaximum::Table(&PALADIN_CHANNEL_DIVINITY),
+        Recovery::OneOnShortRest,
+    ),
+    resource(
+        Class::Ranger,
+        1,
+        "Favored Enemy",
+        ResourceMaximum::Table(&FAVORED_ENEMY),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Rogue,
+        20,
+        "Stroke of Luck",
+        ResourceMaximum::Fixed(1),
+        Recovery::ShortRest,
+    ),
+    resource(
+        Class::Sorcerer,
+        1,
+        "Innate Sorcery",
+        ResourceMaximum::Fixed(2),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Sorcerer,
+        2,
+        "Sorcery Points",
+        ResourceMaximum::ClassLevel(1),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Warlock,
+        2,
+        "Magical Cunning",
+        ResourceMaximum::Fixed(1),
+        Recovery::LongRest,
+    ),
+    resource(
+        Class::Wizard,
+        1,
+        "Arcane Recovery",
+        ResourceMaximum::Fixed(1),
+        Recovery::LongRest,
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_above_the_maximum_use_the_last_row() {
+        let mut character = Character::new();
+        character.classes.insert(Class::Barbarian, MAX_LEVEL + 1);
+        character.resources_spent.push(SpentResource {
+            class: Class::Barbarian,
+            name: "Rage".to_owned(),
+            spent: 1,
+        });
+        assert!(!character.resources().is_empty());
+        let rage = character.resource(Class::Barbarian, "Rage").unwrap();
+
+        character.classes.insert(Class::Barbarian, MAX_LEVEL);
+        assert_eq!(
+            rage.maximum,
+            character
+                .resource(Class::Barbarian, "Rage")
+                .unwrap()
+                .maximum
+        );
+    }
+}
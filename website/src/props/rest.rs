@@ -0,0 +1,201 @@
+use std::fmt;
+
+use super::{Ability, Character, Class, RecoveredResource, Recovery, SpellSlots, MAX_SPELL_LEVEL};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Rest {
+    Short,
+    Long,
+}
+
+/// Hit die spent on a short rest and the number rolled on it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct HitDieRoll {
+    pub class: Class,
+    pub roll: u32,
+}
+
+/// Everything a rest changed, enough to undo it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub struct RestSummary {
+    pub rest: Rest,
+    pub hit_points: u32,
+    pub hit_dice_spent: Vec<HitDieRoll>,
+    pub hit_dice_recovered: Vec<(Class, u32)>,
+    pub resources: Vec<RecoveredResource>,
+    pub spell_slots: SpellSlots,
+    pub pact_slots: u32,
+    pub exhaustion: u32,
+}
+
+impl fmt::Display for RestSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} rest: +{} HP", self.rest, self.hit_points)?;
+        for roll in &self.hit_dice_spent {
+            write!(f, ", spent {:?} hit die ({})", roll.class, roll.roll)?;
+        }
+        for (class, count) in &self.hit_dice_recovered {
+            write!(f, ", {count} {class:?} hit dice back")?;
+        }
+        for resource in &self.resources {
+            write!(f, ", {resource}")?;
+        }
+        for (index, &slots) in self.spell_slots.iter().enumerate() {
+            if slots > 0 {
+                write!(f, ", {slots} level {} slots", index + 1)?;
+            }
+        }
+        if self.pact_slots > 0 {
+            write!(f, ", {} pact slots", self.pact_slots)?;
+        }
+        if self.exhaustion > 0 {
+            write!(f, ", exhaustion -{}", self.exhaustion)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RestError {
+    InvalidHitDieRoll(u32),
+    NoHitDiceLeft(Class),
+}
+
+impl fmt::Display for RestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestError::InvalidHitDieRoll(roll) => write!(f, "Invalid hit die roll: {roll}"),
+            RestError::NoHitDiceLeft(class) => write!(f, "No {class:?} hit dice left"),
+        }
+    }
+}
+
+impl std::error::Error for RestError {}
+
+impl Character {
+    pub fn hit_points(&self) -> u32 {
+        self.max_hit_points().saturating_sub(self.damage)
+    }
+
+    pub fn take_damage(&mut self, amount: u32) {
+        self.damage = self
+            .damage
+            .saturating_add(amount)
+            .min(self.max_hit_points());
+    }
+
+    pub fn heal(&mut self, amount: u32) {
+        self.damage = self
+            .damage
+            .min(self.max_hit_points())
+            .saturating_sub(amount);
+    }
+
+    /// Hit dice left to spend for the class.
+    pub fn hit_dice(&self, class: Class) -> u32 {
+        self.class_level(class)
+            .saturating_sub(self.hit_dice_spent.get(&class).copied().unwrap_or(0))
+    }
+
+    /// Spends the rolled hit dice and recovers short-rest resources and Pact Magic slots.
+    ///
+    /// Nothing is changed if a roll is invalid or the character lacks the hit dice.
+    pub fn short_rest(&mut self, hit_dice: &[HitDieRoll]) -> Result<RestSummary, RestError> {
+        let mut next = self.clone();
+        let con = next.ability_modifier(Ability::Constitution);
+
+        let mut healing = 0;
+        for roll in hit_dice {
+            if !(1..=roll.class.hit_die().sides()).contains(&roll.roll) {
+                return Err(RestError::InvalidHitDieRoll(roll.roll));
+            }
+            if next.hit_dice(roll.class) == 0 {
+                return Err(RestError::NoHitDiceLeft(roll.class));
+            }
+            *next.hit_dice_spent.entry(roll.class).or_insert(0) += 1;
+            healing += (roll.roll as i32 + con).max(0) as u32;
+        }
+
+        let before = next.hit_points();
+        next.heal(healing);
+
+        let summary = RestSummary {
+            rest: Rest::Short,
+            hit_points: next.hit_points() - before,
+            hit_dice_spent: hit_dice.to_vec(),
+            hit_dice_recovered: Vec::new(),
+            resources: next.recover_resources(Recovery::ShortRest),
+            spell_slots: [0; MAX_SPELL_LEVEL as usize],
+            pact_slots: std::mem::take(&mut next.pact_slots_spent),
+            exhaustion: 0,
+        };
+
+        *self = next;
+        Ok(summary)
+    }
+
+    /// Restores hit points, spell slots and long-rest resources, recovers up to
+    /// half of the hit dice and lowers exhaustion by one.
+    pub fn long_rest(&mut self) -> RestSummary {
+        let before = self.hit_points();
+        self.damage = 0;
+
+        // Larger hit dice are recovered first.
+        let mut classes = self.classes().map(|(class, _)| class).collect::<Vec<_>>();
+        classes.sort_by_key(|class| std::cmp::Reverse(class.hit_die().sides()));
+
+        let mut recoverable = (self.total_level() / 2).max(1);
+        let mut hit_dice_recovered = Vec::new();
+        for class in classes {
+            let Some(spent) = self.hit_dice_spent.get_mut(&class) else {
+                continue;
+            };
+            let count = (*spent).min(recoverable);
+            if count > 0 {
+                *spent -= count;
+                recoverable -= count;
+                hit_dice_recovered.push((class, count));
+            }
+        }
+        self.hit_dice_spent.retain(|_, spent| *spent > 0);
+
+        let exhaustion = self.exhaustion.min(1);
+        self.exhaustion -= exhaustion;
+
+        RestSummary {
+            rest: Rest::Long,
+            hit_points: self.hit_points() - before,
+            hit_dice_spent: Vec::new(),
+            hit_dice_recovered,
+            resources: self.recover_resources(Recovery::LongRest),
+            spell_slots: std::mem::take(&mut self.spell_slots_spent),
+            pact_slots: std::mem::take(&mut self.pact_slots_spent),
+            exhaustion,
+        }
+    }
+
+    /// Reverts the changes of a rest taken by this character.
+    pub fn undo_rest(&mut self, summary: &RestSummary) {
+        self.damage += summary.hit_points;
+        for roll in &summary.hit_dice_spent {
+            if let Some(spent) = self.hit_dice_spent.get_mut(&roll.class) {
+                *spent = spent.saturating_sub(1);
+            }
+        }
+        self.hit_dice_spent.retain(|_, spent| *spent > 0);
+        for &(class, count) in &summary.hit_dice_recovered {
+            *self.hit_dice_spent.entry(class).or_insert(0) += count;
+        }
+        for resource in &summary.resources {
+            let spent = self
+                .resource(resource.class, &resource.name)
+                .map_or(0, |state| state.spent);
+            self.write_resource_spent(resource.class, &resource.name, spent + resource.recovered);
+        }
+        for (spent, recovered) in self.spell_slots_spent.iter_mut().zip(summary.spell_slots) {
+            *spent += recovered;
+        }
+        self.pact_slots_spent += summary.pact_slots;
+        self.exhaustion += summary.exhaustion;
+    }
+}
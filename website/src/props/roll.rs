@@ -0,0 +1,183 @@
+use std::fmt;
+
+use super::{
+    total, Ability, Bonus, BonusSource, Character, Dice, Proficiency, RollModifier,
+    EXHAUSTION_D20_PENALTY,
+};
+
+/// Kind of d20 test the character makes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum D20Test {
+    /// Attack with a proficient weapon or spell using the ability.
+    AttackRoll(Ability),
+    AbilityCheck(Ability),
+    SavingThrow(Ability),
+
+    /// Dexterity check to determine the turn order.
+    Initiative,
+}
+
+impl fmt::Display for D20Test {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            D20Test::AttackRoll(ability) => write!(f, "{} attack", ability.abbreviation()),
+            D20Test::AbilityCheck(ability) => write!(f, "{} check", ability.abbreviation()),
+            D20Test::SavingThrow(ability) => write!(f, "{} save", ability.abbreviation()),
+            D20Test::Initiative => f.write_str("Initiative"),
+        }
+    }
+}
+
+/// Everything that goes into a d20 test, with the source of every part.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct D20Roll {
+    pub test: D20Test,
+    pub bonuses: Vec<Bonus>,
+    pub modifiers: Vec<(RollModifier, BonusSource)>,
+}
+
+impl D20Roll {
+    pub fn bonus(&self) -> i32 {
+        total(&self.bonuses)
+    }
+
+    pub fn automatic_failure(&self) -> bool {
+        self.modifiers
+            .iter()
+            .any(|(modifier, _)| *modifier == RollModifier::AutomaticFailure)
+    }
+
+    /// Advantage or Disadvantage left after they cancel each other out.
+    pub fn mode(&self) -> Option<RollModifier> {
+        let has = |mode| self.modifiers.iter().any(|(modifier, _)| *modifier == mode);
+        match (
+            has(RollModifier::Advantage),
+            has(RollModifier::Disadvantage),
+        ) {
+            (true, false) => Some(RollModifier::Advantage),
+            (false, true) => Some(RollModifier::Disadvantage),
+            _ => None,
+        }
+    }
+
+    pub fn roll(&self) -> RollResult {
+        let mut dice = vec![Dice::D20.roll()];
+        let kept = match self.mode() {
+            Some(RollModifier::Advantage) => {
+                dice.push(Dice::D20.roll());
+                dice[0].max(dice[1])
+            }
+            Some(RollModifier::Disadvantage) => {
+                dice.push(Dice::D20.roll());
+                dice[0].min(dice[1])
+            }
+            _ => dice[0],
+        };
+
+        RollResult {
+            dice,
+            total: kept as i32 + self.bonus(),
+            failed: self.automatic_failure(),
+        }
+    }
+}
+
+impl fmt::Display for D20Roll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {:+}", self.test, self.bonus())?;
+        match (self.automatic_failure(), self.mode()) {
+            (true, _) => f.write_str(" fails"),
+            (false, Some(mode)) => write!(f, " with {mode:?}"),
+            (false, None) => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RollResult {
+    pub dice: Vec<u32>,
+    pub total: i32,
+    pub failed: bool,
+}
+
+impl fmt::Display for RollResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dice = self
+            .dice
+            .iter()
+            .map(|die| die.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+        if self.failed {
+            write!(f, "automatic failure (rolled {dice})")
+        } else {
+            write!(f, "{} (rolled {dice})", self.total)
+        }
+    }
+}
+
+impl Character {
+    /// Bonuses, Advantage and Disadvantage of a d20 test.
+    pub fn d20_roll(&self, test: D20Test) -> D20Roll {
+        let mut bonuses = match test {
+            D20Test::AttackRoll(ability) => vec![
+                Bonus::new(
+                    BonusSource::Ability(ability),
+                    self.ability_modifier(ability),
+                ),
+                Bonus::new(BonusSource::Proficiency, self.proficiency_bonus()),
+            ],
+            D20Test::AbilityCheck(ability) => vec![Bonus::new(
+                BonusSource::Ability(ability),
+                self.ability_modifier(ability),
+            )],
+            D20Test::SavingThrow(ability) => {
+                let mut bonuses = vec![Bonus::new(
+                    BonusSource::Ability(ability),
+                    self.ability_modifier(ability),
+                )];
+                let proficient = self
+                    .proficiencies()
+                    .iter()
+                    .any(|(proficiency, _)| *proficiency == Proficiency::SavingThrow(ability));
+                if proficient {
+                    bonuses.push(Bonus::new(
+                        BonusSource::Proficiency,
+                        self.proficiency_bonus(),
+                    ));
+                }
+                bonuses
+            }
+            // Exhaustion is already part of the initiative bonuses.
+            D20Test::Initiative => self.initiative_bonuses(),
+        };
+        if test != D20Test::Initiative {
+            bonuses.extend(self.exhaustion_d20_penalty());
+        }
+
+        let modifiers = self
+            .effective_conditions()
+            .into_iter()
+            .filter_map(|condition| {
+                let modifier = condition.roll_modifier(test)?;
+                Some((modifier, BonusSource::Condition(condition)))
+            })
+            .collect();
+
+        D20Roll {
+            test,
+            bonuses,
+            modifiers,
+        }
+    }
+
+    /// Penalty of exhaustion to every d20 test.
+    pub(super) fn exhaustion_d20_penalty(&self) -> Option<Bonus> {
+        (self.exhaustion > 0).then(|| {
+            Bonus::new(
+                BonusSource::Exhaustion,
+                -EXHAUSTION_D20_PENALTY * self.exhaustion as i32,
+            )
+        })
+    }
+}
@@ -0,0 +1,473 @@
+//! Versioned JSON documents of characters.
+//!
+//! Every stored character carries the `version` of the schema it was written with.
+//! Older documents are upgraded on load by running the migrations from their version
+//! up to [`CHARACTER_SCHEMA_VERSION`], one version at a time.
+//!
+//! Any change to the fields of [`Character`] or the types inside it bumps
+//! [`CHARACTER_SCHEMA_VERSION`], appends a migration from the previous version to
+//! [`MIGRATIONS`] and freezes a JSON fixture and a share code of the new version next
+//! to the existing ones. This holds even for fields with a `#[serde(default)]`: the
+//! compact binary encoding used by exports and share links is not self-describing,
+//! so a new field shifts every value after it, and only binary documents of the
+//! current version can be read.
+//!
+//! Characters stored before versioning were kept in the binary encoding of the storage
+//! rather than as JSON. [`Character::from_legacy_binary`] reads them into a version 0
+//! document, which is then migrated like any other.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::{json, Map, Value};
+
+use super::{Character, Class, Morality, Order, Species, ABILITY_SCORE_IMPROVEMENT};
+
+/// Version of the schema written by [`Character::to_json`].
+pub const CHARACTER_SCHEMA_VERSION: u32 = 1;
+
+type Migration = fn(&mut Map<String, Value>) -> Result<(), SchemaError>;
+
+/// Classes in the order of their variants before Warlock was added, as indexed by the
+/// binary encoding of characters stored before versioning.
+const LEGACY_CLASSES: [Class; 12] = [
+    Class::Artificer,
+    Class::Barbarian,
+    Class::Bard,
+    Class::Cleric,
+    Class::Druid,
+    Class::Fighter,
+    Class::Monk,
+    Class::Paladin,
+    Class::Ranger,
+    Class::Rogue,
+    Class::Sorcerer,
+    Class::Wizard,
+];
+
+/// Migration at index `n` upgrades a document from version `n` to version `n + 1`.
+static MIGRATIONS: [Migration; CHARACTER_SCHEMA_VERSION as usize] = [v0_to_v1];
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SchemaError {
+    Json(String),
+    Binary(String),
+    NotAnObject,
+    UnsupportedVersion(u32),
+    Migration { version: u32, reason: String },
+}
+
+impl fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SchemaError::Json(err) => write!(f, "Invalid character JSON: {err}"),
+            SchemaError::Binary(err) => write!(f, "Invalid character data: {err}"),
+            SchemaError::NotAnObject => write!(f, "Character JSON must be an object"),
+            SchemaError::UnsupportedVersion(version) => write!(
+                f,
+                "Character version {version} is newer than the supported version {CHARACTER_SCHEMA_VERSION}"
+            ),
+            SchemaError::Migration { version, reason } => {
+                write!(f, "Cannot upgrade character from version {version}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for SchemaError {}
+
+impl From<serde_json::Error> for SchemaError {
+    fn from(err: serde_json::Error) -> Self {
+        SchemaError::Json(err.to_string())
+    }
+}
+
+impl From<postcard::Error> for SchemaError {
+    fn from(err: postcard::Error) -> Self {
+        SchemaError::Binary(err.to_string())
+    }
+}
+
+impl From<base64::DecodeError> for SchemaError {
+    fn from(err: base64::DecodeError) -> Self {
+        SchemaError::Binary(err.to_string())
+    }
+}
+
+impl Character {
+    /// Reads a character document of any supported version.
+    pub fn from_json(json: &str) -> Result<Self, SchemaError> {
+        let value = serde_json::from_str(json)?;
+        Self::from_json_value(value)
+    }
+
+    pub fn from_json_value(value: Value) -> Result<Self, SchemaError> {
+        let Value::Object(mut document) = value else {
+            return Err(SchemaError::NotAnObject);
+        };
+
+        // Documents written before versioning have no version field.
+        let version = match document.remove("version") {
+            None => 0,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| SchemaError::Json(format!("invalid version {version}")))?,
+        };
+        if version > CHARACTER_SCHEMA_VERSION {
+            return Err(SchemaError::UnsupportedVersion(version));
+        }
+
+        for migration in &MIGRATIONS[version as usize..] {
+            migration(&mut document)?;
+        }
+
+        Ok(serde_json::from_value(Value::Object(document))?)
+    }
+
+    /// Writes the character as a document of the current version.
+    pub fn to_json(&self) -> String {
+        self.to_json_value().to_string()
+    }
+
+    pub fn to_json_value(&self) -> Value {
+        let mut value = serde_json::to_value(self).expect("character serializes to JSON");
+        if let Value::Object(document) = &mut value {
+            document.insert("version".to_owned(), json!(CHARACTER_SCHEMA_VERSION));
+        }
+        value
+    }
+
+    /// Reads a character from the compact binary encoding of the current version.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, SchemaError> {
+        let (version, bytes) = postcard::take_from_bytes::<u32>(bytes)?;
+        match version.cmp(&CHARACTER_SCHEMA_VERSION) {
+            Ordering::Greater => Err(SchemaError::UnsupportedVersion(version)),
+            Ordering::Less => Err(SchemaError::Migration {
+                version,
+                reason: "binary documents cannot be upgraded".to_owned(),
+            }),
+            Ordering::Equal => Ok(postcard::from_bytes(bytes)?),
+        }
+    }
+
+    /// Writes the character through the non-human-readable serde path, prefixed with the version.
+    pub fn to_binary(&self) -> Vec<u8> {
+        postcard::to_allocvec(&(CHARACTER_SCHEMA_VERSION, self))
+            .expect("character serializes to binary")
+    }
+
+    /// Reads a character stored before versioning, from the binary encoding of the storage.
+    pub fn from_legacy_binary(bytes: &[u8]) -> Result<Self, SchemaError> {
+        // Classes were left out of the encoding when there were none.
+        let document = legacy_document(bytes, true).or_else(|_| legacy_document(bytes, false))?;
+        Self::from_json_value(document)
+    }
+
+    /// Reads a character from the base64url text of a share link.
+    pub fn from_share_code(code: &str) -> Result<Self, SchemaError> {
+        Self::from_binary(&URL_SAFE_NO_PAD.decode(code.trim())?)
+    }
+
+    /// Binary encoding as base64url text, safe to put into a URL.
+    pub fn to_share_code(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.to_binary())
+    }
+}
+
+/// Version 0 document of a character stored before versioning, with or without its
+/// classes, failing unless every byte is read.
+fn legacy_document(bytes: &[u8], with_classes: bool) -> Result<Value, SchemaError> {
+    let (name, bytes) = postcard::take_from_bytes::<String>(bytes)?;
+    let (species, bytes) = postcard::take_from_bytes::<Species>(bytes)?;
+    let (classes, bytes) = match with_classes {
+        true => postcard::take_from_bytes::<Vec<(u32, u32)>>(bytes)?,
+        false => (Vec::new(), bytes),
+    };
+    let ((order, morality), bytes) = postcard::take_from_bytes::<(Order, Morality)>(bytes)?;
+    let (origin, bytes) = postcard::take_from_bytes::<String>(bytes)?;
+    let ([str, dex, con, int, wis, cha], bytes) = postcard::take_from_bytes::<[u32; 6]>(bytes)?;
+    if !bytes.is_empty() {
+        return Err(SchemaError::Binary(format!(
+            "{} bytes left over",
+            bytes.len()
+        )));
+    }
+
+    let mut class_levels = Map::new();
+    for (index, level) in classes {
+        let class = LEGACY_CLASSES
+            .get(index as usize)
+            .ok_or_else(|| SchemaError::Binary(format!("unknown class {index}")))?;
+        class_levels.insert(format!("{class:?}"), json!(level));
+    }
+    Ok(json!({
+        "name": name,
+        "species": species,
+        "classes": class_levels,
+        "alignment": format!("{order:?} {morality:?}"),
+        "origin": origin,
+        "str": str,
+        "dex": dex,
+        "con": con,
+        "int": int,
+        "wis": wis,
+        "cha": cha,
+    }))
+}
+
+/// Upgrades a level choice written before feats carried their sub-choices, either
+/// `{"AbilityScoreImprovement": [ability, ability]}` or `{"Feat": name}`.
+fn upgrade_level_choice(choice: &mut Value) {
+    let Value::Object(choice) = choice else {
+        return;
+    };
+    if let Some(abilities) = choice.remove("AbilityScoreImprovement") {
+        let choices = abilities
+            .as_array()
+            .into_iter()
+            .flatten()
+            .map(|ability| json!({ "Ability": ability }))
+            .collect::<Vec<_>>();
+        choice.insert(
+            "Feat".to_owned(),
+            json!({ "name": ABILITY_SCORE_IMPROVEMENT, "choices": choices }),
+        );
+    } else if let Some(Value::String(name)) = choice.get("Feat") {
+        let feat = json!({ "name": name, "choices": [] });
+        choice.insert("Feat".to_owned(), feat);
+    }
+}
+
+/// Version 0 kept only the level of each class; version 1 records every level gained.
+///
+/// The history is rebuilt with the classes in a fixed order, taking the maximum of the
+/// hit die for the first level and the average for the others. Histories recorded
+/// before versioning are kept, with their feats upgraded to carry sub-choices.
+fn v0_to_v1(document: &mut Map<String, Value>) -> Result<(), SchemaError> {
+    if let Some(levels) = document.get_mut("levels") {
+        let choices = levels
+            .as_array_mut()
+            .into_iter()
+            .flatten()
+            .filter_map(|level| level.get_mut("choices")?.as_array_mut())
+            .flatten();
+        for choice in choices {
+            upgrade_level_choice(choice);
+        }
+        return Ok(());
+    }
+
+    let migration_error = |reason: String| SchemaError::Migration { version: 0, reason };
+
+    let mut classes = Vec::new();
+    if let Some(Value::Object(map)) = document.get("classes") {
+        for (name, level) in map {
+            let class = Class::from_str(name)
+                .map_err(|_| migration_error(format!("unknown class {name}")))?;
+            let level = level
+                .as_u64()
+                .ok_or_else(|| migration_error(format!("invalid level of {name}")))?;
+            classes.push((class, level as u32));
+        }
+    }
+    classes.sort_by_key(|(class, _)| Class::ALL.iter().position(|c| c == class));
+
+    let mut levels = Vec::new();
+    for (class, level) in classes {
+        let sides = class.hit_die().sides();
+        for class_level in 1..=level {
+            let hit_points = if levels.is_empty() {
+                sides
+            } else {
+                sides / 2 + 1
+            };
+            levels.push(json!({
+                "class": class,
+                "class_level": class_level,
+                "hit_points": hit_points,
+            }));
+        }
+    }
+    document.insert("levels".to_owned(), Value::Array(levels));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::props::{Ability, ChosenFeat, FeatChoice, LevelUpChoice};
+
+    /// Frozen documents, one for every schema version, oldest first.
+    const FIXTURES: [&str; CHARACTER_SCHEMA_VERSION as usize + 1] = [
+        include_str!("../../tests/fixtures/character_v0.json"),
+        include_str!("../../tests/fixtures/character_v1.json"),
+    ];
+
+    /// Document written before versioning with the level history of the first release,
+    /// whose feats had no sub-choices.
+    const FIXTURE_V0_LEVELS: &str = include_str!("../../tests/fixtures/character_v0_levels.json");
+
+    /// Share code of the fixture of the current version, frozen to catch changes of the
+    /// binary layout that did not bump the version.
+    const SHARE_CODE: &str = include_str!("../../tests/fixtures/character_v1.share");
+
+    #[test]
+    fn every_fixture_loads() {
+        for (version, fixture) in FIXTURES.iter().enumerate() {
+            let character = Character::from_json(fixture)
+                .unwrap_or_else(|err| panic!("version {version}: {err}"));
+            assert_eq!(character.name, "Tordek", "version {version}");
+        }
+    }
+
+    #[test]
+    fn legacy_binary_is_migrated() {
+        let character =
+            Character::from_legacy_binary(include_bytes!("../../tests/fixtures/character_v0.bin"))
+                .unwrap();
+        assert_eq!(character, Character::from_json(FIXTURES[0]).unwrap());
+
+        // The only character the first release could store, without classes.
+        let character = Character::from_legacy_binary(include_bytes!(
+            "../../tests/fixtures/character_v0_new.bin"
+        ))
+        .unwrap();
+        assert_eq!(character.name, "<unnamed>");
+        assert_eq!(character.total_level(), 0);
+        assert_eq!(character.ability_score(Ability::Strength), 10);
+
+        // Wizard came right after Sorcerer before Warlock was added.
+        let wizard = [
+            9, 60, 117, 110, 110, 97, 109, 101, 100, 62, 0, 1, 11, 2, 1, 1, 12, 60, 110, 111, 116,
+            32, 99, 104, 111, 115, 101, 110, 62, 10, 10, 10, 10, 10, 10,
+        ];
+        let character = Character::from_legacy_binary(&wizard).unwrap();
+        assert_eq!(character.class_level(Class::Wizard), 2);
+    }
+
+    #[test]
+    fn v0_rebuilds_level_history() {
+        let character = Character::from_json(FIXTURES[0]).unwrap();
+
+        assert_eq!(character.total_level(), 4);
+        assert_eq!(character.class_level(Class::Cleric), 1);
+        assert_eq!(character.class_level(Class::Fighter), 3);
+        assert_eq!(character.levels().len(), 4);
+        assert_eq!(character.levels()[0].class, Class::Cleric);
+        assert_eq!(character.levels()[0].hit_points, 8);
+        assert_eq!(character.levels()[1].hit_points, 6);
+        // 8 + 6 * 3 from hit dice and +2 Constitution for each of the four levels.
+        assert_eq!(character.max_hit_points(), 34);
+        assert_eq!(character.ability_score(Ability::Strength), 16);
+    }
+
+    #[test]
+    fn v0_level_history_gets_feat_choices() {
+        let character = Character::from_json(FIXTURE_V0_LEVELS).unwrap();
+
+        assert_eq!(character.levels().len(), 8);
+        assert_eq!(
+            character.levels()[2].choices,
+            [LevelUpChoice::Subclass("Champion".to_owned())]
+        );
+        assert_eq!(
+            character.levels()[3].choices,
+            [LevelUpChoice::Feat(ChosenFeat {
+                name: ABILITY_SCORE_IMPROVEMENT.to_owned(),
+                choices: vec![
+                    FeatChoice::Ability(Ability::Strength),
+                    FeatChoice::Ability(Ability::Constitution),
+                ],
+            })]
+        );
+        assert_eq!(
+            character.levels()[5].choices,
+            [LevelUpChoice::Feat(ChosenFeat::new("Charger"))]
+        );
+        assert_eq!(character.ability_score(Ability::Strength), 17);
+    }
+
+    #[test]
+    fn v1_keeps_play_state() {
+        let character = Character::from_json(FIXTURES[1]).unwrap();
+
+        assert_eq!(character.ability_score(Ability::Strength), 18);
+        assert_eq!(character.hit_points(), character.max_hit_points() - 5);
+        assert_eq!(character.exhaustion(), 1);
+        assert_eq!(character.conditions().len(), 1);
+        assert_eq!(character.spell_slots_spent()[0], 1);
+        assert_eq!(
+            character
+                .resource(Class::Fighter, "Second Wind")
+                .map(|state| state.spent),
+            Ok(1)
+        );
+    }
+
+    #[test]
+    fn current_version_round_trips() {
+        let character = Character::from_json(FIXTURES[CHARACTER_SCHEMA_VERSION as usize]).unwrap();
+        let json = character.to_json();
+
+        assert_eq!(Character::from_json(&json).unwrap(), character);
+        assert_eq!(
+            character.to_json_value()["version"],
+            json!(CHARACTER_SCHEMA_VERSION)
+        );
+    }
+
+    #[test]
+    fn binary_round_trips() {
+        let character = Character::from_json(FIXTURES[CHARACTER_SCHEMA_VERSION as usize]).unwrap();
+        let binary = character.to_binary();
+
+        assert!(binary.len() < character.to_json().len());
+        assert_eq!(Character::from_binary(&binary).unwrap(), character);
+        assert_eq!(
+            Character::from_share_code(&character.to_share_code()).unwrap(),
+            character
+        );
+    }
+
+    #[test]
+    fn frozen_share_code_loads() {
+        let character = Character::from_json(FIXTURES[CHARACTER_SCHEMA_VERSION as usize]).unwrap();
+
+        assert_eq!(Character::from_share_code(SHARE_CODE).unwrap(), character);
+    }
+
+    #[test]
+    fn older_binary_is_rejected() {
+        let character = Character::from_json(FIXTURES[0]).unwrap();
+        let mut binary = character.to_binary();
+        binary[0] = 0;
+
+        assert!(matches!(
+            Character::from_binary(&binary),
+            Err(SchemaError::Migration { version: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let json = json!({ "version": CHARACTER_SCHEMA_VERSION + 1 }).to_string();
+
+        assert_eq!(
+            Character::from_json(&json),
+            Err(SchemaError::UnsupportedVersion(
+                CHARACTER_SCHEMA_VERSION + 1
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_class_fails_migration() {
+        let json = json!({ "classes": { "Necromancer": 1 } }).to_string();
+
+        assert!(matches!(
+            Character::from_json(&json),
+            Err(SchemaError::Migration { version: 0, .. })
+        ));
+    }
+}
@@ -0,0 +1,298 @@
+//! Character sheets for the lore articles and for printing.
+//!
+//! The Markdown sheet follows the layout of the articles in `resources/articles/characters`:
+//! the name as the only first-level heading, a few `Label: value` lines, then a
+//! second-level heading for every section. The HTML sheet is the same Markdown
+//! rendered into a standalone page with its own styles.
+
+use std::fmt;
+
+use pulldown_cmark::{html, Options, Parser};
+
+use super::{Ability, Character, D20Test, MAX_SPELL_LEVEL};
+
+/// Styles of the HTML sheet, kept inline so that the file works on its own.
+const SHEET_CSS: &str = "\
+body { font-family: Georgia, serif; max-width: 48rem; margin: 2rem auto; padding: 0 1rem; color: #222; }
+h1 { border-bottom: 2px solid #7a200d; }
+h2 { color: #7a200d; border-bottom: 1px solid #ccc; }
+table { border-collapse: collapse; }
+th, td { border: 1px solid #ccc; padding: 0.25rem 0.75rem; text-align: left; }
+@media print { body { margin: 0; max-width: none; } h2 { break-after: avoid; } }
+";
+
+/// Escapes the characters that Markdown would read as formatting.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(
+            c,
+            '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|'
+        ) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl Character {
+    /// Full character sheet as Markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = String::new();
+        self.write_markdown(&mut markdown)
+            .expect("writing into a string cannot fail");
+        markdown
+    }
+
+    fn write_markdown(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        let classes = self.classes().collect::<Vec<_>>();
+
+        writeln!(out, "# {}", escape(&self.name))?;
+        writeln!(out)?;
+        writeln!(out, "Species: {:?}\\", self.species)?;
+        if !classes.is_empty() {
+            let class_text = classes
+                .iter()
+                .map(|&(class, level)| match self.subclass(class) {
+                    Some(subclass) => format!("{class:?} {level} ({})", escape(subclass)),
+                    None => format!("{class:?} {level}"),
+                })
+                .collect::<Vec<_>>()
+                .join(" / ");
+            writeln!(out, "Class: {class_text}\\")?;
+        }
+        writeln!(out, "Level: {}\\", self.total_level())?;
+        if let Some(background) = &self.background {
+            writeln!(out, "Background: {}\\", escape(&background.name))?;
+        }
+        writeln!(
+            out,
+            "Alignment: {:?} {:?}\\",
+            self.alignment.0, self.alignment.1
+        )?;
+        writeln!(out, "Origin: {}", escape(&self.origin))?;
+
+        writeln!(out)?;
+        writeln!(out, "## Abilities")?;
+        writeln!(out)?;
+        writeln!(out, "| Ability | Score | Modifier | Saving Throw |")?;
+        writeln!(out, "| --- | --- | --- | --- |")?;
+        for ability in Ability::ALL {
+            writeln!(
+                out,
+                "| {ability:?} | {} | {:+} | {:+} |",
+                self.ability_score(ability),
+                self.ability_modifier(ability),
+                self.d20_roll(D20Test::SavingThrow(ability)).bonus(),
+            )?;
+        }
+
+        writeln!(out)?;
+        writeln!(out, "## Combat")?;
+        writeln!(out)?;
+        writeln!(
+            out,
+            "- Hit Points: {} / {}",
+            self.hit_points(),
+            self.max_hit_points()
+        )?;
+        writeln!(out, "- Armor Class: {}", self.armor_class())?;
+        let hit_dice = classes
+            .iter()
+            .map(|&(class, level)| {
+                format!(
+                    "{}/{level} d{}",
+                    self.hit_dice(class),
+                    class.hit_die().sides()
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        if !hit_dice.is_empty() {
+            writeln!(out, "- Hit Dice: {hit_dice}")?;
+        }
+        writeln!(
+            out,
+            "- Initiative: {:+}",
+            self.d20_roll(D20Test::Initiative).bonus()
+        )?;
+        writeln!(out, "- Speed: {} ft", self.speed())?;
+        writeln!(out, "- Proficiency Bonus: {:+}", self.proficiency_bonus())?;
+
+        let proficiencies = self.proficiencies();
+        if !proficiencies.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Proficiencies")?;
+            writeln!(out)?;
+            for (proficiency, source) in proficiencies {
+                writeln!(out, "- {} ({source})", escape(&proficiency.to_string()))?;
+            }
+        }
+
+        let feats = self.feats().collect::<Vec<_>>();
+        if !feats.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Feats")?;
+            writeln!(out)?;
+            for (source, feat) in feats {
+                writeln!(out, "- {} ({source})", escape(&feat.to_string()))?;
+            }
+        }
+
+        let resources = self.resources();
+        if !resources.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Resources")?;
+            writeln!(out)?;
+            for state in resources {
+                writeln!(
+                    out,
+                    "- {}: {} / {} ({})",
+                    state.resource.name,
+                    state.available(),
+                    state.maximum,
+                    state.recovery
+                )?;
+            }
+        }
+
+        let slots = self.spell_slots();
+        let pact = self.pact_slots();
+        if slots.iter().any(|&count| count > 0) || pact.count > 0 {
+            writeln!(out)?;
+            writeln!(out, "## Spell Slots")?;
+            writeln!(out)?;
+            writeln!(out, "| Level | Available | Total |")?;
+            writeln!(out, "| --- | --- | --- |")?;
+            let spent = self.spell_slots_spent();
+            for level in 1..=MAX_SPELL_LEVEL {
+                let index = level as usize - 1;
+                if slots[index] > 0 {
+                    writeln!(
+                        out,
+                        "| {level} | {} | {} |",
+                        slots[index].saturating_sub(spent[index]),
+                        slots[index]
+                    )?;
+                }
+            }
+            if pact.count > 0 {
+                writeln!(
+                    out,
+                    "| Pact {} | {} | {} |",
+                    pact.level,
+                    pact.count.saturating_sub(self.pact_slots_spent()),
+                    pact.count
+                )?;
+            }
+        }
+
+        if self.exhaustion() > 0 || !self.conditions().is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Conditions")?;
+            writeln!(out)?;
+            if self.exhaustion() > 0 {
+                writeln!(out, "- Exhaustion {}", self.exhaustion())?;
+            }
+            for active in self.conditions() {
+                writeln!(out, "- {}", escape(&active.to_string()))?;
+            }
+        }
+
+        if !self.equipment.is_empty() {
+            writeln!(out)?;
+            writeln!(out, "## Equipment")?;
+            writeln!(out)?;
+            for item in &self.equipment {
+                writeln!(out, "- {}", escape(item.name()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Printable character sheet as a self-contained HTML document.
+    pub fn to_html(&self) -> String {
+        let markdown = self.to_markdown();
+
+        let mut body = String::new();
+        html::push_html(
+            &mut body,
+            Parser::new_ext(&markdown, Options::ENABLE_TABLES),
+        );
+
+        let mut title = String::new();
+        pulldown_cmark_escape::escape_html(&mut title, &self.name)
+            .expect("escaping into a string cannot fail");
+
+        format!(
+            "<!DOCTYPE html>\n\
+             <html lang=\"en\">\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <title>{title}</title>\n\
+             <style>\n{SHEET_CSS}</style>\n\
+             </head>\n\
+             <body>\n{body}</body>\n\
+             </html>\n"
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, path::Path};
+
+    use super::*;
+
+    /// Compares the output with the stored snapshot, or rewrites the snapshot
+    /// when `UPDATE_SNAPSHOTS` is set.
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/snapshots")
+            .join(name);
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::write(&path, actual).unwrap();
+            return;
+        }
+
+        let expected = fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("missing snapshot {}: {err}", path.display()));
+        assert_eq!(
+            actual, expected,
+            "snapshot {name} changed, rerun with UPDATE_SNAPSHOTS=1 to accept"
+        );
+    }
+
+    fn tordek() -> Character {
+        Character::from_json(include_str!("../../tests/fixtures/character_v1.json")).unwrap()
+    }
+
+    #[test]
+    fn markdown_sheet() {
+        assert_snapshot("sheet.md", &tordek().to_markdown());
+    }
+
+    #[test]
+    fn html_sheet() {
+        assert_snapshot("sheet.html", &tordek().to_html());
+    }
+
+    #[test]
+    fn new_character_sheet() {
+        assert_snapshot("sheet_new.md", &Character::new().to_markdown());
+    }
+
+    #[test]
+    fn markdown_is_escaped() {
+        let mut character = tordek();
+        character.name = "*Tordek* <the | Bold>".to_owned();
+
+        let markdown = character.to_markdown();
+        assert!(markdown.starts_with("# \\*Tordek\\* \\<the \\| Bold\\>\n"));
+        assert!(character
+            .to_html()
+            .contains("<title>*Tordek* &lt;the | Bold&gt;</title>"));
+    }
+}
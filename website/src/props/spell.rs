@@ -0,0 +1,178 @@
+use std::fmt;
+
+use super::{Character, Class};
+
+/// Highest spell level.
+pub const MAX_SPELL_LEVEL: u32 = 9;
+
+/// How a class contributes to the shared spell slots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Spellcasting {
+    Full,
+
+    /// Half of the class level, rounded up.
+    Half,
+
+    /// Pact Magic slots, separate from the shared ones.
+    Pact,
+}
+
+impl Class {
+    pub fn spellcasting(self) -> Option<Spellcasting> {
+        match self {
+            Class::Bard | Class::Cleric | Class::Druid | Class::Sorcerer | Class::Wizard => {
+                Some(Spellcasting::Full)
+            }
+            Class::Artificer | Class::Paladin | Class::Ranger => Some(Spellcasting::Half),
+            Class::Warlock => Some(Spellcasting::Pact),
+            Class::Barbarian | Class::Fighter | Class::Monk | Class::Rogue => None,
+        }
+    }
+}
+
+/// Spell slots by spell level, lowest level first.
+pub type SpellSlots = [u32; MAX_SPELL_LEVEL as usize];
+
+/// Pact Magic slots of a Warlock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PactSlots {
+    pub count: u32,
+    pub level: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SpellSlotError {
+    InvalidLevel(u32),
+    NoSlotsLeft(u32),
+    NoPactSlotsLeft,
+}
+
+impl fmt::Display for SpellSlotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpellSlotError::InvalidLevel(level) => write!(f, "Invalid spell slot level: {level}"),
+            SpellSlotError::NoSlotsLeft(level) => write!(f, "No level {level} spell slots left"),
+            SpellSlotError::NoPactSlotsLeft => write!(f, "No Pact Magic slots left"),
+        }
+    }
+}
+
+impl std::error::Error for SpellSlotError {}
+
+impl Character {
+    /// Spellcaster level used to look up the shared spell slots.
+    pub fn caster_level(&self) -> u32 {
+        self.classes()
+            .map(|(class, level)| match class.spellcasting() {
+                Some(Spellcasting::Full) => level,
+                Some(Spellcasting::Half) => level.div_ceil(2),
+                Some(Spellcasting::Pact) | None => 0,
+            })
+            .sum()
+    }
+
+    pub fn spell_slots(&self) -> SpellSlots {
+        match self.caster_level() {
+            0 => [0; MAX_SPELL_LEVEL as usize],
+            level => SPELL_SLOTS[level.min(20) as usize - 1],
+        }
+    }
+
+    pub fn spell_slots_spent(&self) -> SpellSlots {
+        self.spell_slots_spent
+    }
+
+    pub fn pact_slots(&self) -> PactSlots {
+        match self.class_level(Class::Warlock) {
+            0 => PactSlots { count: 0, level: 0 },
+            level => PACT_SLOTS[level.min(20) as usize - 1],
+        }
+    }
+
+    pub fn pact_slots_spent(&self) -> u32 {
+        self.pact_slots_spent
+    }
+
+    pub fn spend_spell_slot(&mut self, level: u32) -> Result<(), SpellSlotError> {
+        if !(1..=MAX_SPELL_LEVEL).contains(&level) {
+            return Err(SpellSlotError::InvalidLevel(level));
+        }
+        let index = level as usize - 1;
+        if self.spell_slots_spent[index] >= self.spell_slots()[index] {
+            return Err(SpellSlotError::NoSlotsLeft(level));
+        }
+        self.spell_slots_spent[index] += 1;
+        Ok(())
+    }
+
+    pub fn restore_spell_slot(&mut self, level: u32) -> Result<(), SpellSlotError> {
+        if !(1..=MAX_SPELL_LEVEL).contains(&level) {
+            return Err(SpellSlotError::InvalidLevel(level));
+        }
+        let spent = &mut self.spell_slots_spent[level as usize - 1];
+        *spent = spent.saturating_sub(1);
+        Ok(())
+    }
+
+    pub fn spend_pact_slot(&mut self) -> Result<(), SpellSlotError> {
+        if self.pact_slots_spent >= self.pact_slots().count {
+            return Err(SpellSlotError::NoPactSlotsLeft);
+        }
+        self.pact_slots_spent += 1;
+        Ok(())
+    }
+
+    pub fn restore_pact_slot(&mut self) {
+        self.pact_slots_spent = self.pact_slots_spent.saturating_sub(1);
+    }
+}
+
+static SPELL_SLOTS: [SpellSlots; 20] = [
+    [2, 0, 0, 0, 0, 0, 0, 0, 0],
+    [3, 0, 0, 0, 0, 0, 0, 0, 0],
+    [4, 2, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 0, 0, 0, 0, 0, 0, 0],
+    [4, 3, 2, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 0, 0, 0, 0, 0, 0],
+    [4, 3, 3, 1, 0, 0, 0, 0, 0],
+    [4, 3, 3, 2, 0, 0, 0, 0, 0],
+    [4, 3, 3, 3, 1, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 0, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 0, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 0, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 0],
+    [4, 3, 3, 3, 2, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 1, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 1, 1, 1],
+    [4, 3, 3, 3, 3, 2, 2, 1, 1],
+];
+
+const fn pact(count: u32, level: u32) -> PactSlots {
+    PactSlots { count, level }
+}
+
+static PACT_SLOTS: [PactSlots; 20] = [
+    pact(1, 1),
+    pact(2, 1),
+    pact(2, 2),
+    pact(2, 2),
+    pact(2, 3),
+    pact(2, 3),
+    pact(2, 4),
+    pact(2, 4),
+    pact(2, 5),
+    pact(2, 5),
+    pact(3, 5),
+    pact(3, 5),
+    pact(3, 5),
+    pact(3, 5),
+    pact(3, 5),
+    pact(3, 5),
+    pact(4, 5),
+    pact(4, 5),
+    pact(4, 5),
+    pact(4, 5),
+];
@@ -0,0 +1,290 @@
+use std::fmt;
+
+use super::{Ability, Character, Class, EquipmentProperties, MAX_EXHAUSTION, MAX_LEVEL};
+
+/// Lowest and highest ability score allowed by the rules.
+pub const ABILITY_SCORE_RANGE: (u32, u32) = (1, 30);
+
+/// Range of base ability scores reachable with the standard generation methods.
+pub const BASE_ABILITY_SCORE_RANGE: (u32, u32) = (3, 20);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// The character breaks the rules or its own data.
+    Error,
+
+    /// The character is legal but unusual, such as a house rule.
+    Warning,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum IssueKind {
+    EmptyName,
+    AbilityScoreOutOfRange(Ability, u32),
+    UnusualAbilityScore(Ability, u32),
+    TotalLevelAboveMaximum(u32),
+    ClassLevelMismatch {
+        class: Class,
+        class_level: u32,
+        recorded: u32,
+    },
+    MulticlassPrerequisite(Class),
+    UnknownFeat(String),
+    UnknownBackground(String),
+    MultipleArmor(usize),
+    MultipleShields(usize),
+    DamageAboveMaximum(u32),
+    ExhaustionAboveMaximum(u32),
+    SpellSlotsAboveMaximum {
+        level: u32,
+        spent: u32,
+    },
+    UnknownResource(String),
+}
+
+impl fmt::Display for IssueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IssueKind::EmptyName => write!(f, "The character has no name"),
+            IssueKind::AbilityScoreOutOfRange(ability, score) => write!(
+                f,
+                "{ability:?} {score} is outside of {}-{}",
+                ABILITY_SCORE_RANGE.0, ABILITY_SCORE_RANGE.1
+            ),
+            IssueKind::UnusualAbilityScore(ability, score) => write!(
+                f,
+                "Base {ability:?} {score} is outside of the usual {}-{}",
+                BASE_ABILITY_SCORE_RANGE.0, BASE_ABILITY_SCORE_RANGE.1
+            ),
+            IssueKind::TotalLevelAboveMaximum(level) => {
+                write!(f, "Total level {level} is above {MAX_LEVEL}")
+            }
+            IssueKind::ClassLevelMismatch {
+                class,
+                class_level,
+                recorded,
+            } => write!(
+                f,
+                "{class:?} level is {class_level}, but {recorded} levels are recorded"
+            ),
+            IssueKind::MulticlassPrerequisite(class) => {
+                write!(f, "Multiclass prerequisites of {class:?} are not met")
+            }
+            IssueKind::UnknownFeat(name) => write!(f, "Unknown feat: {name}"),
+            IssueKind::UnknownBackground(name) => write!(f, "Unknown background: {name}"),
+            IssueKind::MultipleArmor(count) => write!(f, "{count} suits of armor are equipped"),
+            IssueKind::MultipleShields(count) => write!(f, "{count} shields are equipped"),
+            IssueKind::DamageAboveMaximum(damage) => {
+                write!(f, "Damage {damage} is above the maximum hit points")
+            }
+            IssueKind::ExhaustionAboveMaximum(level) => {
+                write!(f, "Exhaustion {level} is above {MAX_EXHAUSTION}")
+            }
+            IssueKind::SpellSlotsAboveMaximum { level, spent } => {
+                write!(
+                    f,
+                    "{spent} level {level} spell slots spent, more than available"
+                )
+            }
+            IssueKind::UnknownResource(name) => write!(f, "Unknown resource: {name}"),
+        }
+    }
+}
+
+/// Problem found in a character, with the path of the field it is about.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+    pub path: String,
+    pub kind: IssueKind,
+}
+
+impl fmt::Display for ValidationIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} at {}: {}", self.severity, self.path, self.kind)
+    }
+}
+
+impl Character {
+    /// Checks the character for broken rules and data, errors first.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        let mut issue = |severity, path: String, kind| {
+            issues.push(ValidationIssue {
+                severity,
+                path,
+                kind,
+            })
+        };
+
+        if self.name.trim().is_empty() {
+            issue(Severity::Error, "name".to_owned(), IssueKind::EmptyName);
+        }
+
+        for ability in Ability::ALL {
+            let path = ability_path(ability).to_owned();
+            let score = self.base_ability_score(ability);
+            if !(ABILITY_SCORE_RANGE.0..=ABILITY_SCORE_RANGE.1).contains(&score) {
+                issue(
+                    Severity::Error,
+                    path,
+                    IssueKind::AbilityScoreOutOfRange(ability, score),
+                );
+            } else if !(BASE_ABILITY_SCORE_RANGE.0..=BASE_ABILITY_SCORE_RANGE.1).contains(&score) {
+                issue(
+                    Severity::Warning,
+                    path,
+                    IssueKind::UnusualAbilityScore(ability, score),
+                );
+            }
+        }
+
+        let total_level = self.total_level();
+        if total_level > MAX_LEVEL {
+            issue(
+                Severity::Error,
+                "classes".to_owned(),
+                IssueKind::TotalLevelAboveMaximum(total_level),
+            );
+        }
+
+        let classes = self.classes().collect::<Vec<_>>();
+        for &(class, class_level) in &classes {
+            let recorded = self
+                .levels
+                .iter()
+                .filter(|level| level.class == class)
+                .count() as u32;
+            if recorded != class_level {
+                issue(
+                    Severity::Error,
+                    format!("classes.{class:?}"),
+                    IssueKind::ClassLevelMismatch {
+                        class,
+                        class_level,
+                        recorded,
+                    },
+                );
+            }
+
+            let score = |ability| self.ability_score(ability);
+            if classes.len() > 1 && !class.multiclass_prerequisite().is_met(score) {
+                issue(
+                    Severity::Warning,
+                    format!("classes.{class:?}"),
+                    IssueKind::MulticlassPrerequisite(class),
+                );
+            }
+        }
+
+        for (index, level) in self.levels.iter().enumerate() {
+            for feat in level.feats() {
+                if feat.feat().is_none() {
+                    issue(
+                        Severity::Error,
+                        format!("levels[{index}].choices"),
+                        IssueKind::UnknownFeat(feat.name.clone()),
+                    );
+                }
+            }
+        }
+        for (index, feat) in self.feats.iter().enumerate() {
+            if feat.feat().is_none() {
+                issue(
+                    Severity::Error,
+                    format!("feats[{index}]"),
+                    IssueKind::UnknownFeat(feat.name.clone()),
+                );
+            }
+        }
+
+        if let Some(background) = &self.background {
+            if background.background().is_none() {
+                issue(
+                    Severity::Error,
+                    "background.name".to_owned(),
+                    IssueKind::UnknownBackground(background.name.clone()),
+                );
+            }
+        }
+
+        let armor = self
+            .equipment
+            .iter()
+            .filter(|item| matches!(item.properties(), EquipmentProperties::Armor(_)))
+            .count();
+        if armor > 1 {
+            issue(
+                Severity::Error,
+                "equipment".to_owned(),
+                IssueKind::MultipleArmor(armor),
+            );
+        }
+        let shields = self
+            .equipment
+            .iter()
+            .filter(|item| matches!(item.properties(), EquipmentProperties::Shield(_)))
+            .count();
+        if shields > 1 {
+            issue(
+                Severity::Error,
+                "equipment".to_owned(),
+                IssueKind::MultipleShields(shields),
+            );
+        }
+
+        if self.damage > self.max_hit_points() {
+            issue(
+                Severity::Warning,
+                "damage".to_owned(),
+                IssueKind::DamageAboveMaximum(self.damage),
+            );
+        }
+        if self.exhaustion > MAX_EXHAUSTION {
+            issue(
+                Severity::Error,
+                "exhaustion".to_owned(),
+                IssueKind::ExhaustionAboveMaximum(self.exhaustion),
+            );
+        }
+
+        let spell_slots = self.spell_slots().into_iter().zip(self.spell_slots_spent);
+        for (index, (slots, spent)) in spell_slots.enumerate() {
+            if spent > slots {
+                issue(
+                    Severity::Warning,
+                    format!("spell_slots_spent[{index}]"),
+                    IssueKind::SpellSlotsAboveMaximum {
+                        level: index as u32 + 1,
+                        spent,
+                    },
+                );
+            }
+        }
+
+        for (index, spent) in self.resources_spent.iter().enumerate() {
+            if self.resource(spent.class, &spent.name).is_err() {
+                issue(
+                    Severity::Warning,
+                    format!("resources_spent[{index}]"),
+                    IssueKind::UnknownResource(spent.name.clone()),
+                );
+            }
+        }
+
+        issues.sort_by_key(|issue| issue.severity);
+        issues
+    }
+}
+
+/// Name of the serialized field holding the base ability score.
+fn ability_path(ability: Ability) -> &'static str {
+    match ability {
+        Ability::Strength => "str",
+        Ability::Dexterity => "dex",
+        Ability::Constitution => "con",
+        Ability::Intelligence => "int",
+        Ability::Wisdom => "wis",
+        Ability::Charisma => "cha",
+    }
+}
@@ -0,0 +1,281 @@
+//! Full-text search over the articles embedded from `resources/articles`.
+//!
+//! Articles are split into sections at their headings and every word is reduced to its
+//! stem, so that `Дафны` finds `Дафна` and `кузнецу` finds `кузнец`.
+
+use std::ops::Range;
+
+use dioxus::prelude::*;
+
+use crate::{
+    articles,
+    link::article_route,
+    lore::{term, words, ArticleEntry},
+};
+
+/// Most results shown for a query.
+const MAX_RESULTS: usize = 20;
+
+/// Characters of the section text shown on each side of the first match.
+const SNIPPET_RADIUS: usize = 60;
+
+/// Weight of a query word found in the heading of a section, against one in its text.
+const HEADING_WEIGHT: f32 = 3.0;
+
+/// Sections of the listed articles, split by the build script.
+static INDEX: &[Section] = include!(concat!(env!("OUT_DIR"), "/search_index.rs"));
+
+/// Part of an article from one heading to the next.
+#[derive(Debug)]
+struct Section {
+    /// Path of the article.
+    article: &'static str,
+
+    /// Id of the heading, empty for the text before the first heading.
+    id: &'static str,
+    heading: &'static str,
+    text: &'static str,
+
+    /// Stems of the words of the heading, sorted.
+    heading_terms: &'static [&'static str],
+
+    /// Start and end bytes in the text of the words with each stem, sorted by the stem.
+    terms: &'static [(&'static str, &'static [(usize, usize)])],
+}
+
+impl Section {
+    fn has_heading_term(&self, term: &str) -> bool {
+        self.heading_terms.binary_search(&term).is_ok()
+    }
+
+    /// Start and end bytes in the text of the words with the stem.
+    fn term_ranges(&self, term: &str) -> Option<&'static [(usize, usize)]> {
+        let found = self.terms.binary_search_by_key(&term, |&(term, _)| term);
+        found.ok().map(|i| self.terms[i].1)
+    }
+}
+
+/// Piece of a snippet, highlighted when it is a word of the query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetPart {
+    pub text: String,
+    pub highlight: bool,
+}
+
+/// Section of an article that matches a query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchResult {
+    pub article: &'static ArticleEntry,
+
+    /// Id of the heading of the section, empty for the text before the first heading.
+    pub section: String,
+    pub heading: String,
+    pub score: f32,
+
+    /// Text around the first match in the section.
+    pub snippet: Vec<SnippetPart>,
+}
+
+/// Text of the section around its first match, with the matched words highlighted.
+///
+/// The snippet of a section matched by its heading alone starts the text.
+fn snippet(text: &str, mut matches: Vec<Range<usize>>) -> Vec<SnippetPart> {
+    matches.sort_by_key(|range| range.start);
+    let first = matches.first().cloned().unwrap_or(0..0);
+
+    // Cut at the spaces nearest to the radius, so that no word is cut in half.
+    let before = text[..first.start]
+        .char_indices()
+        .rev()
+        .nth(SNIPPET_RADIUS)
+        .map_or(0, |(i, _)| {
+            text[i..first.start]
+                .find(' ')
+                .map_or(first.start, |space| i + space + 1)
+        });
+    let after =
+        text[first.end..]
+            .char_indices()
+            .nth(SNIPPET_RADIUS)
+            .map_or(text.len(), |(i, _)| {
+                let i = first.end + i;
+                text[first.end..i]
+                    .rfind(' ')
+                    .map_or(i, |space| first.end + space)
+            });
+
+    let mut parts = Vec::new();
+    let mut push = |range: Range<usize>, highlight| {
+        if !range.is_empty() {
+            parts.push(SnippetPart {
+                text: text[range].to_owned(),
+                highlight,
+            });
+        }
+    };
+    let mut at = before;
+    for range in matches
+        .into_iter()
+        .filter(|range| range.start >= before && range.end <= after)
+    {
+        push(at..range.start, false);
+        push(range.clone(), true);
+        at = range.end;
+    }
+    push(at..after, false);
+
+    if before > 0 {
+        parts.insert(
+            0,
+            SnippetPart {
+                text: "…".to_owned(),
+                highlight: false,
+            },
+        );
+    }
+    if after < text.len() {
+        parts.push(SnippetPart {
+            text: "…".to_owned(),
+            highlight: false,
+        });
+    }
+    parts
+}
+
+fn search_in(index: &[Section], query: &str) -> Vec<SearchResult> {
+    let mut query_terms = words(query).map(|(_, word)| term(word)).collect::<Vec<_>>();
+    query_terms.sort();
+    query_terms.dedup();
+    if query_terms.is_empty() {
+        return Vec::new();
+    }
+
+    // Rare words weigh more than the ones found in most sections.
+    let count = index.len() as f32;
+    let rarity = |term: &String| {
+        let found = index
+            .iter()
+            .filter(|section| section.term_ranges(term).is_some() || section.has_heading_term(term))
+            .count() as f32;
+        (count / found.max(1.0)).ln() + 1.0
+    };
+    let rarities = query_terms.iter().map(rarity).collect::<Vec<_>>();
+
+    let mut results = index
+        .iter()
+        .filter_map(|section| {
+            let mut score = 0.0;
+            let mut matches = Vec::new();
+            for (term, rarity) in query_terms.iter().zip(&rarities) {
+                let in_heading = section.has_heading_term(term);
+                let ranges = section.term_ranges(term);
+                if !in_heading && ranges.is_none() {
+                    return None;
+                }
+                if in_heading {
+                    score += HEADING_WEIGHT * rarity;
+                }
+                if let Some(ranges) = ranges {
+                    score += (1.0 + (ranges.len() as f32).ln()) * rarity;
+                    matches.extend(ranges.iter().map(|&(start, end)| start..end));
+                }
+            }
+            Some(SearchResult {
+                article: articles::article(section.article)?,
+                section: section.id.to_owned(),
+                heading: section.heading.to_owned(),
+                score,
+                snippet: snippet(section.text, matches),
+            })
+        })
+        .collect::<Vec<_>>();
+    results.sort_by(|a, b| b.score.total_cmp(&a.score));
+    results.truncate(MAX_RESULTS);
+    results
+}
+
+/// Sections of the articles that have every word of the query, best matches first.
+///
+/// Unlisted articles are left out.
+pub fn search(query: &str) -> Vec<SearchResult> {
+    search_in(INDEX, query)
+}
+
+/// Search field with the matching sections listed below it.
+#[component]
+pub fn SearchBox() -> Element {
+    let mut query = use_signal(String::new);
+    let results = use_memo(move || search(&query.read()));
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2",
+            input {
+                class: "px-2 py-1 bg-gray-100",
+                r#type: "search",
+                placeholder: "Search articles",
+                value: "{query}",
+                oninput: move |e| query.set(e.value()),
+            }
+            if !query.read().trim().is_empty() {
+                div {
+                    class: "flex flex-col gap-2 w-72 text-sm",
+                    if results.read().is_empty() {
+                        span { class: "text-gray-600", "Nothing found" }
+                    }
+                    for result in results.read().iter().cloned() {
+                        Link {
+                            key: "{result.article.path}#{result.section}",
+                            class: "flex flex-col px-2 py-1 bg-gray-100 hover:bg-amber-200",
+                            to: article_route(result.article.path, &result.section),
+                            onclick: move |_| query.set(String::new()),
+                            span {
+                                class: "font-bold",
+                                if result.heading.is_empty() || result.heading == result.article.title {
+                                    "{result.article.title}"
+                                } else {
+                                    "{result.article.title} › {result.heading}"
+                                }
+                            }
+                            span {
+                                for part in result.snippet {
+                                    if part.highlight {
+                                        mark { "{part.text}" }
+                                    } else {
+                                        "{part.text}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn russian_words_are_found_by_their_stem() {
+        assert_eq!(term("Дафны"), term("Дафна"));
+        assert_eq!(term("Ёжик"), term("ежика"));
+
+        let results = search("Дафны");
+        assert_eq!(results[0].article.path, "characters/Dafna");
+        assert_eq!(results[0].section, "дафна");
+        assert!(results[1]
+            .snippet
+            .iter()
+            .any(|part| part.highlight && part.text == "Дафну"));
+
+        let results = search("кузнецу Валреку");
+        assert!(!results.is_empty());
+        assert!(results
+            .iter()
+            .all(|result| result.article.path == "campaigns/Tournament/session_1"));
+        assert!(search("").is_empty());
+    }
+}
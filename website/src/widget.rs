@@ -0,0 +1,543 @@
+//! Interactive components embedded in articles as fenced code blocks.
+//!
+//! The first word of the info string picks the widget and the body of the block is
+//! its YAML data:
+//!
+//! ````markdown
+//! ```dice
+//! roll: 8d6
+//! label: Fireball
+//! ```
+//! ````
+//!
+//! Blocks of other languages are shown as code.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use dioxus::prelude::*;
+use serde::de::DeserializeOwned;
+
+use crate::{
+    character::ArticleStatBlock,
+    props::{ability_modifier, Ability, Dice},
+};
+
+/// Renders the body of a fenced code block.
+pub type Widget = fn(&str) -> Result<Element, WidgetError>;
+
+/// Widgets by the language of the blocks they render.
+static WIDGETS: &[(&str, Widget)] = &[
+    ("statblock", stat_block),
+    ("dice", dice),
+    ("rolltable", roll_table),
+];
+
+/// Widget that renders fenced code blocks of the language.
+pub fn widget(language: &str) -> Option<Widget> {
+    WIDGETS
+        .iter()
+        .find(|(name, _)| *name == language)
+        .map(|&(_, widget)| widget)
+}
+
+/// Why a widget could not be built from the body of its block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WidgetError {
+    Yaml(String),
+    Invalid(String),
+}
+
+impl fmt::Display for WidgetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WidgetError::Yaml(err) => write!(f, "Invalid YAML: {err}"),
+            WidgetError::Invalid(err) => f.write_str(err),
+        }
+    }
+}
+
+impl std::error::Error for WidgetError {}
+
+fn parse<T: DeserializeOwned>(body: &str) -> Result<T, WidgetError> {
+    serde_yaml::from_str(body).map_err(|err| WidgetError::Yaml(err.to_string()))
+}
+
+/// Fenced code block of the language rendered by its widget, or the error with the
+/// source of the block when it cannot be.
+#[component]
+pub fn CodeBlockWidget(language: String, body: String) -> Element {
+    let Some(widget) = widget(&language) else {
+        return rsx! {
+            pre { code { "{body}" } }
+        };
+    };
+
+    match widget(&body) {
+        Ok(element) => element,
+        Err(err) => rsx! {
+            div {
+                class: "flex flex-col gap-2 p-2 border-2 border-red-700",
+                span { class: "text-red-700", "Invalid {language} block: {err}" }
+                pre { code { "{body}" } }
+            }
+        },
+    }
+}
+
+/// Most dice a roll may have, so that rolling it stays quick.
+const MAX_DICE: i32 = 100;
+
+/// Sum of dice and a flat bonus, written as `2d6 + 1d4 - 1`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DiceRoll {
+    /// Count of each die, negative when the dice are subtracted.
+    pub dice: Vec<(i32, Dice)>,
+    pub bonus: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InvalidDiceRoll(String);
+
+impl fmt::Display for InvalidDiceRoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for InvalidDiceRoll {}
+
+impl FromStr for DiceRoll {
+    type Err = InvalidDiceRoll;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidDiceRoll(s.to_owned());
+        let text = s.replace(char::is_whitespace, "").to_lowercase();
+        if text.is_empty() {
+            return Err(invalid());
+        }
+
+        let mut roll = DiceRoll {
+            dice: Vec::new(),
+            bonus: 0,
+        };
+        // Split before every sign, keeping it with the term that follows.
+        let mut terms = Vec::new();
+        let mut start = 0;
+        for (i, c) in text.char_indices() {
+            if i > 0 && matches!(c, '+' | '-') {
+                terms.push(&text[start..i]);
+                start = i;
+            }
+        }
+        terms.push(&text[start..]);
+
+        let mut dice_count = 0;
+        for term in terms {
+            match term.split_once('d') {
+                Some((count, sides)) => {
+                    let (sign, count) = match count.strip_prefix('-') {
+                        Some(count) => (-1, count),
+                        None => (1, count.strip_prefix('+').unwrap_or(count)),
+                    };
+                    let count = match count {
+                        "" => 1,
+                        count => count.parse::<i32>().map_err(|_| invalid())?,
+                    };
+                    if count == 0 || count > MAX_DICE - dice_count {
+                        return Err(invalid());
+                    }
+                    dice_count += count;
+                    let sides = sides.parse().map_err(|_| invalid())?;
+                    let die = Dice::from_sides(sides).ok_or_else(invalid)?;
+                    roll.dice.push((sign * count, die));
+                }
+                None => {
+                    // Parsed with its sign, so that the smallest bonus fits.
+                    let bonus = term.parse::<i32>().map_err(|_| invalid())?;
+                    roll.bonus = roll.bonus.checked_add(bonus).ok_or_else(invalid)?;
+                }
+            }
+        }
+        Ok(roll)
+    }
+}
+
+impl fmt::Display for DiceRoll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (count, die)) in self.dice.iter().enumerate() {
+            match (i, *count < 0) {
+                (0, false) => {}
+                (0, true) => f.write_str("-")?,
+                (_, false) => f.write_str(" + ")?,
+                (_, true) => f.write_str(" - ")?,
+            }
+            write!(f, "{}d{}", count.abs(), die.sides())?;
+        }
+        match (self.dice.is_empty(), self.bonus) {
+            (true, bonus) => write!(f, "{bonus}"),
+            (false, 0) => Ok(()),
+            (false, bonus) if bonus < 0 => write!(f, " - {}", -bonus),
+            (false, bonus) => write!(f, " + {bonus}"),
+        }
+    }
+}
+
+impl DiceRoll {
+    /// Rolls every die, returning what each one rolled and the total.
+    pub fn roll(&self) -> (Vec<u32>, i32) {
+        let mut rolled = Vec::new();
+        let mut total = self.bonus;
+        for &(count, die) in &self.dice {
+            for _ in 0..count.abs() {
+                let value = die.roll();
+                rolled.push(value);
+                total = total.saturating_add(count.signum() * value as i32);
+            }
+        }
+        (rolled, total)
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum DiceBlock {
+    Roll(String),
+    Labeled { roll: String, label: Option<String> },
+}
+
+/// Button rolling the dice of a ```` ```dice ```` block: either the roll alone, such as
+/// `2d6 + 3`, or a `roll` with a `label`.
+fn dice(body: &str) -> Result<Element, WidgetError> {
+    let (roll, label) = match parse(body)? {
+        DiceBlock::Roll(roll) => (roll, None),
+        DiceBlock::Labeled { roll, label } => (roll, label),
+    };
+    let roll = roll
+        .parse::<DiceRoll>()
+        .map_err(|err| WidgetError::Invalid(err.to_string()))?;
+
+    Ok(rsx! {
+        DiceButton { roll, label }
+    })
+}
+
+#[component]
+fn DiceButton(roll: DiceRoll, label: Option<String>) -> Element {
+    let mut result = use_signal(|| None::<(Vec<u32>, i32)>);
+    let roll_text = roll.to_string();
+
+    rsx! {
+        div {
+            class: "flex items-center gap-2",
+            button {
+                class: "px-2 py-1 bg-amber-200 hover:bg-amber-300 rounded",
+                onclick: move |_| result.set(Some(roll.roll())),
+                match &label {
+                    Some(label) => rsx! { "{label} ({roll_text})" },
+                    None => rsx! { "Roll {roll_text}" },
+                }
+            }
+            if let Some((rolled, total)) = &*result.read() {
+                span {
+                    title: "{rolled:?}",
+                    class: "font-bold",
+                    "{total}"
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+#[serde(untagged)]
+enum RollTableRow {
+    Result(String),
+    Weighted { result: String, weight: u32 },
+}
+
+impl RollTableRow {
+    fn result(&self) -> &str {
+        match self {
+            RollTableRow::Result(result) | RollTableRow::Weighted { result, .. } => result,
+        }
+    }
+
+    fn weight(&self) -> u32 {
+        match self {
+            RollTableRow::Result(_) => 1,
+            RollTableRow::Weighted { weight, .. } => *weight,
+        }
+    }
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct RollTableBlock {
+    title: Option<String>,
+    rows: Vec<RollTableRow>,
+}
+
+/// Random table of a ```` ```rolltable ```` block, with a `title` and `rows` that are
+/// either results or a `result` with the `weight` of its chance.
+fn roll_table(body: &str) -> Result<Element, WidgetError> {
+    let table = parse::<RollTableBlock>(body)?;
+    if table.rows.is_empty() {
+        return Err(WidgetError::Invalid("The table has no rows".to_owned()));
+    }
+    if let Some(row) = table.rows.iter().find(|row| row.weight() == 0) {
+        return Err(WidgetError::Invalid(format!(
+            "Row {:?} has a weight of 0",
+            row.result()
+        )));
+    }
+
+    // Numbers of the die that give each row, counting from 1.
+    let mut rows = Vec::new();
+    let mut last = 0u32;
+    for row in &table.rows {
+        let end = last.checked_add(row.weight()).ok_or_else(|| {
+            WidgetError::Invalid("The weights of the rows add up to too much".to_owned())
+        })?;
+        rows.push((last + 1, end, row.result().to_owned()));
+        last = end;
+    }
+
+    Ok(rsx! {
+        RollTable { title: table.title, rows, sides: last }
+    })
+}
+
+#[component]
+fn RollTable(title: Option<String>, rows: Vec<(u32, u32, String)>, sides: u32) -> Element {
+    let mut rolled = use_signal(|| None::<u32>);
+
+    rsx! {
+        div {
+            class: "flex flex-col gap-2",
+            div {
+                class: "flex items-center gap-2",
+                if let Some(title) = &title {
+                    span { class: "text-xl", "{title}" }
+                }
+                button {
+                    class: "px-2 py-1 bg-amber-200 hover:bg-amber-300 rounded",
+                    onclick: move |_| rolled.set(Some(rand::random_range(1..=sides))),
+                    "Roll d{sides}"
+                }
+                if let Some(rolled) = rolled() {
+                    span { class: "font-bold", "{rolled}" }
+                }
+            }
+            table {
+                class: "table-auto text-left",
+                thead {
+                    tr {
+                        th { class: "px-2", "d{sides}" }
+                        th { class: "px-2", "Result" }
+                    }
+                }
+                tbody {
+                    for (first, last, result) in rows {
+                        tr {
+                            class: if rolled().is_some_and(|rolled| (first..=last).contains(&rolled)) { "bg-amber-200" },
+                            td {
+                                class: "px-2",
+                                if first == last { "{first}" } else { "{first}–{last}" }
+                            }
+                            td { class: "px-2", "{result}" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+struct Feature {
+    name: String,
+    text: String,
+}
+
+/// Stat block of a ```` ```statblock ````: a stored `character` by its id or one
+/// embedded by its share `code`, or else a monster written out in the block.
+#[derive(Debug, serde::Deserialize)]
+struct StatBlockData {
+    character: Option<String>,
+    code: Option<String>,
+
+    name: Option<String>,
+
+    /// Size, type and alignment, such as `Medium beast, unaligned`.
+    #[serde(default)]
+    kind: String,
+    armor_class: Option<String>,
+    hit_points: Option<String>,
+    speed: Option<String>,
+
+    /// Scores by ability, 10 for the ones left out.
+    #[serde(default)]
+    abilities: HashMap<Ability, u32>,
+    #[serde(default)]
+    traits: Vec<Feature>,
+    #[serde(default)]
+    actions: Vec<Feature>,
+}
+
+fn stat_block(body: &str) -> Result<Element, WidgetError> {
+    let data = parse::<StatBlockData>(body)?;
+    if data.character.is_some() || data.code.is_some() {
+        return Ok(rsx! {
+            ArticleStatBlock { id: data.character, code: data.code }
+        });
+    }
+
+    let name = data.name.ok_or_else(|| {
+        WidgetError::Invalid("Either a character, a code or a name is required".to_owned())
+    })?;
+    let abilities =
+        Ability::ALL.map(|ability| (ability, data.abilities.get(&ability).copied().unwrap_or(10)));
+
+    Ok(rsx! {
+        MonsterBlock {
+            name,
+            kind: data.kind,
+            armor_class: data.armor_class,
+            hit_points: data.hit_points,
+            speed: data.speed,
+            abilities,
+            traits: data.traits,
+            actions: data.actions,
+        }
+    })
+}
+
+#[component]
+fn MonsterBlock(
+    name: String,
+    kind: String,
+    armor_class: Option<String>,
+    hit_points: Option<String>,
+    speed: Option<String>,
+    abilities: [(Ability, u32); 6],
+    traits: Vec<Feature>,
+    actions: Vec<Feature>,
+) -> Element {
+    rsx! {
+        aside {
+            class: "flex flex-col gap-2 p-4 w-96 border-2 border-amber-800 bg-amber-50",
+            span { class: "text-2xl", "{name}" }
+            if !kind.is_empty() {
+                span { class: "italic", "{kind}" }
+            }
+            div {
+                class: "flex flex-col",
+                if let Some(armor_class) = armor_class {
+                    span { "Armor Class {armor_class}" }
+                }
+                if let Some(hit_points) = hit_points {
+                    span { "Hit Points {hit_points}" }
+                }
+                if let Some(speed) = speed {
+                    span { "Speed {speed}" }
+                }
+            }
+            div {
+                class: "grid grid-cols-6 gap-1 text-center",
+                for (ability, score) in abilities {
+                    div {
+                        class: "flex flex-col",
+                        span { class: "text-xs", "{ability.abbreviation()}" }
+                        span { "{score}" }
+                        span { class: "text-sm", "({ability_modifier(score):+})" }
+                    }
+                }
+            }
+            for feature in traits {
+                p {
+                    span { class: "font-bold italic", "{feature.name}. " }
+                    "{feature.text}"
+                }
+            }
+            if !actions.is_empty() {
+                span { class: "text-xl border-b border-amber-800", "Actions" }
+                for feature in actions {
+                    p {
+                        span { class: "font-bold italic", "{feature.name}. " }
+                        "{feature.text}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dice_rolls_are_parsed() {
+        let roll = "2d6 + d4 - 1".parse::<DiceRoll>().unwrap();
+        assert_eq!(roll.dice, [(2, Dice::D6), (1, Dice::D4)]);
+        assert_eq!(roll.bonus, -1);
+        assert_eq!(roll.to_string(), "2d6 + 1d4 - 1");
+
+        let (rolled, total) = roll.roll();
+        assert_eq!(rolled.len(), 3);
+        assert!((2..=15).contains(&total));
+
+        let roll = "100d4 + 1".parse::<DiceRoll>().unwrap();
+        assert_eq!(roll.roll().0.len(), 100);
+        assert_eq!(
+            "2147483647 - 1".parse::<DiceRoll>().map(|roll| roll.bonus),
+            Ok(i32::MAX - 1)
+        );
+        assert_eq!(
+            "-2147483648".parse::<DiceRoll>().map(|roll| roll.bonus),
+            Ok(i32::MIN)
+        );
+
+        for invalid in [
+            "",
+            "2d7",
+            "d",
+            "0d6",
+            "1d6 - 0d4",
+            "2d6 +",
+            "fireball",
+            "101d6",
+            "60d6 + 41d4",
+            "2147483647 + 1",
+            "-2147483647 - 2",
+            "2147483647d6",
+            "60d6 + 2147483647d4",
+        ] {
+            assert!(invalid.parse::<DiceRoll>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn blocks_are_parsed_as_yaml() {
+        assert!(dice("8d6").is_ok());
+        assert!(dice("roll: 8d6\nlabel: Fireball").is_ok());
+        assert_eq!(
+            dice("roll: 8d7").err(),
+            Some(WidgetError::Invalid("8d7".to_owned()))
+        );
+        assert!(roll_table("rows: [Goblins, {result: Ogre, weight: 2}]").is_ok());
+        assert!(matches!(
+            roll_table("rows: []"),
+            Err(WidgetError::Invalid(_))
+        ));
+        assert!(matches!(
+            roll_table("rows: [{result: Dragon, weight: 4294967295}, Goblins]"),
+            Err(WidgetError::Invalid(_))
+        ));
+        assert!(matches!(
+            stat_block("name: [Wolf"),
+            Err(WidgetError::Yaml(_))
+        ));
+        assert!(stat_block("name: Wolf\nabilities: {Strength: 12}").is_ok());
+        assert!(widget("rust").is_none());
+    }
+}